@@ -0,0 +1,217 @@
+use chrono::{DateTime, Local};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const LOG_FILE_NAME: &str = "app.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_ROTATED_LOGS: usize = 10;
+const MAX_SNIPPET_CHARS: usize = 400;
+
+struct ActiveLog {
+    file: File,
+    day: String,
+    bytes: u64,
+}
+
+struct FileLogger {
+    dir: PathBuf,
+    active: Mutex<Option<ActiveLog>>,
+}
+
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn file_day(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    Some(DateTime::<Local>::from(modified).format("%Y-%m-%d").to_string())
+}
+
+impl FileLogger {
+    fn current_path(&self) -> PathBuf {
+        self.dir.join(LOG_FILE_NAME)
+    }
+
+    fn open(&self) -> std::io::Result<ActiveLog> {
+        let path = self.current_path();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let bytes = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+        Ok(ActiveLog {
+            file,
+            day: file_day(&path).unwrap_or_else(today),
+            bytes,
+        })
+    }
+
+    // Rotated files are named app.log.<day>.<n> so they sort chronologically by name.
+    fn rotate(&self, day: &str) {
+        let current = self.current_path();
+        let mut index = 1;
+        let mut target = self.dir.join(format!("{LOG_FILE_NAME}.{day}.{index}"));
+        while target.exists() {
+            index += 1;
+            target = self.dir.join(format!("{LOG_FILE_NAME}.{day}.{index}"));
+        }
+        let _ = fs::rename(&current, &target);
+
+        let mut rotated = rotated_logs(&self.dir);
+        while rotated.len() > MAX_ROTATED_LOGS {
+            let _ = fs::remove_file(rotated.remove(0));
+        }
+    }
+
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        let mut active = self.active.lock().map_err(|_| std::io::Error::other("log lock poisoned"))?;
+        let today = today();
+        let needs_rotation = active
+            .as_ref()
+            .map(|log| log.day != today || log.bytes + line.len() as u64 > MAX_LOG_BYTES)
+            .unwrap_or(false);
+        if needs_rotation {
+            let day = active.take().map(|log| log.day).unwrap_or_else(|| today.clone());
+            self.rotate(&day);
+        }
+        if active.is_none() {
+            *active = Some(self.open()?);
+        }
+
+        let log = active.as_mut().expect("log file opened above");
+        log.file.write_all(line.as_bytes())?;
+        log.bytes += line.len() as u64;
+        Ok(())
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} [{}] {}\n",
+            Local::now().to_rfc3339(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if self.write_line(&line).is_err() {
+            eprint!("{line}");
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut active) = self.active.lock() {
+            if let Some(log) = active.as_mut() {
+                let _ = log.file.flush();
+            }
+        }
+    }
+}
+
+fn rotated_logs(dir: &Path) -> Vec<PathBuf> {
+    let prefix = format!("{LOG_FILE_NAME}.");
+    let mut rotated: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|items| {
+            items
+                .filter_map(|item| item.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .map(|name| name.starts_with(&prefix))
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    rotated.sort_by_key(|path| {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("").to_string();
+        let (day, index) = name[prefix.len()..].rsplit_once('.').unwrap_or((&name, "0"));
+        (day.to_string(), index.parse::<u32>().unwrap_or(0))
+    });
+    rotated
+}
+
+pub(crate) fn log_dir(base_data_dir: &Path) -> PathBuf {
+    base_data_dir.join("logs")
+}
+
+pub(crate) fn init(log_dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(log_dir).map_err(|e| format!("Failed to create log directory: {e}"))?;
+    log::set_boxed_logger(Box::new(FileLogger {
+        dir: log_dir.to_path_buf(),
+        active: Mutex::new(None),
+    }))
+    .map_err(|e| format!("Failed to install logger: {e}"))?;
+    log::set_max_level(LevelFilter::Info);
+    Ok(())
+}
+
+/// Returns the last `lines` lines across the current and most recent rotated log files.
+pub(crate) fn recent_lines(log_dir: &Path, lines: usize) -> Result<Vec<String>, String> {
+    let mut files = rotated_logs(log_dir);
+    files.push(log_dir.join(LOG_FILE_NAME));
+
+    let mut collected: Vec<String> = Vec::new();
+    for path in files.iter().rev() {
+        if collected.len() >= lines {
+            break;
+        }
+        let Ok(file) = File::open(path) else {
+            continue;
+        };
+        let file_lines: Vec<String> = BufReader::new(file).lines().map_while(Result::ok).collect();
+        let needed = lines - collected.len();
+        let start = file_lines.len().saturating_sub(needed);
+        let mut chunk = file_lines[start..].to_vec();
+        chunk.append(&mut collected);
+        collected = chunk;
+    }
+    Ok(collected)
+}
+
+/// Trims child-process output to its tail so a log line stays readable.
+pub(crate) fn snippet(text: &str) -> String {
+    let trimmed = text.trim();
+    let count = trimmed.chars().count();
+    if count <= MAX_SNIPPET_CHARS {
+        return trimmed.replace('\n', " | ");
+    }
+    let tail: String = trimmed.chars().skip(count - MAX_SNIPPET_CHARS).collect();
+    format!("...{}", tail.replace('\n', " | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_lines_spans_rotated_files_in_order() {
+        let dir = std::env::temp_dir().join(format!("app-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("app.log.2024-01-01.1"), "a\nb\n").unwrap();
+        fs::write(dir.join("app.log.2024-01-02.1"), "c\nd\n").unwrap();
+        fs::write(dir.join("app.log.2024-01-02.2"), "e\n").unwrap();
+        fs::write(dir.join("app.log"), "f\ng\n").unwrap();
+
+        assert_eq!(recent_lines(&dir, 4).unwrap(), vec!["d", "e", "f", "g"]);
+        assert_eq!(recent_lines(&dir, 100).unwrap().len(), 7);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn snippet_keeps_the_tail_of_long_output() {
+        let long = format!("{}\nfatal: boom", "x".repeat(1000));
+        let result = snippet(&long);
+        assert!(result.starts_with("..."));
+        assert!(result.ends_with("| fatal: boom"));
+    }
+}
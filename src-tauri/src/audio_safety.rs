@@ -0,0 +1,185 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Safety copies live next to the audio they protect, in `entries/<id>/audio/.safety`.
+const SAFETY_DIR: &str = ".safety";
+// Followed by three digits of milliseconds and a `Z`; chrono cannot parse `%3f` back without a dot,
+// and a dot would end the file stem.
+const STAMP_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A recording as it was just before a destructive audio operation. `id` is the file stem,
+/// `<timestamp>-<operation>`, so copies sort oldest to newest by name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SafetyCopy {
+    pub(crate) id: String,
+    pub(crate) operation: String,
+    pub(crate) created_at: String,
+    pub(crate) size_bytes: u64,
+}
+
+pub(crate) fn safety_dir(audio_dir: &Path) -> PathBuf {
+    audio_dir.join(SAFETY_DIR)
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn format_stamp(at: NaiveDateTime) -> String {
+    format!("{}{:03}Z", at.format(STAMP_FORMAT), at.and_utc().timestamp_subsec_millis())
+}
+
+fn stamp_of(id: &str) -> Option<NaiveDateTime> {
+    let (stamp, _) = id.split_once('-')?;
+    let (seconds, millis) = stamp.strip_suffix('Z')?.split_at_checked(15)?;
+    let at = NaiveDateTime::parse_from_str(seconds, STAMP_FORMAT).ok()?;
+    Some(at + chrono::Duration::milliseconds(millis.parse().ok()?))
+}
+
+fn copy_files(audio_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(items) = fs::read_dir(safety_dir(audio_dir)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(String, PathBuf)> = items
+        .flatten()
+        .map(|item| item.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            valid_id(&stem).then_some((stem, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Links (or, across filesystems, copies) `source` into the safety directory and then prunes down
+/// to `keep` copies. Recordings are only ever replaced, never rewritten in place, so a hard link
+/// keeps the old audio even after the operation removes the original name. `keep == 0` turns
+/// safety copies off.
+pub(crate) fn preserve(
+    audio_dir: &Path,
+    source: &Path,
+    operation: &str,
+    keep: usize,
+) -> Result<Option<PathBuf>, String> {
+    if keep == 0 || !source.exists() {
+        return Ok(None);
+    }
+    let dir = safety_dir(audio_dir);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create audio safety directory: {e}"))?;
+    let extension = source.extension().and_then(|ext| ext.to_str()).unwrap_or("wav");
+    // Names must sort in creation order, so a copy made within the same millisecond as the newest
+    // one is stamped just after it.
+    let newest = copy_files(audio_dir).last().and_then(|(id, _)| stamp_of(id));
+    let now = Utc::now().naive_utc();
+    let sub_millis = now.and_utc().timestamp_subsec_nanos() % 1_000_000;
+    let now = now - chrono::Duration::nanoseconds(i64::from(sub_millis));
+    let created = match newest {
+        Some(newest) if newest >= now => newest + chrono::Duration::milliseconds(1),
+        _ => now,
+    };
+    let target = dir.join(format!("{}-{operation}.{extension}", format_stamp(created)));
+    if fs::hard_link(source, &target).is_err() {
+        fs::copy(source, &target).map_err(|e| format!("Failed to save an audio safety copy: {e}"))?;
+    }
+    prune(audio_dir, keep);
+    Ok(Some(target))
+}
+
+/// Newest first.
+pub(crate) fn list(audio_dir: &Path) -> Vec<SafetyCopy> {
+    copy_files(audio_dir)
+        .into_iter()
+        .rev()
+        .map(|(id, path)| {
+            let operation = id.split_once('-').map(|(_, operation)| operation).unwrap_or_default();
+            SafetyCopy {
+                operation: operation.to_string(),
+                created_at: stamp_of(&id).map(|created| created.and_utc().to_rfc3339()).unwrap_or_default(),
+                size_bytes: fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0),
+                id,
+            }
+        })
+        .collect()
+}
+
+/// Removes all but the `keep` newest copies and returns how many went.
+pub(crate) fn prune(audio_dir: &Path, keep: usize) -> usize {
+    let files = copy_files(audio_dir);
+    let excess = files.len().saturating_sub(keep);
+    files
+        .into_iter()
+        .take(excess)
+        .filter(|(_, path)| fs::remove_file(path).is_ok())
+        .count()
+}
+
+pub(crate) fn find(audio_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if !valid_id(id) {
+        return Err("Invalid safety copy id".to_string());
+    }
+    copy_files(audio_dir)
+        .into_iter()
+        .find(|(stem, _)| stem == id)
+        .map(|(_, path)| path)
+        .ok_or_else(|| "Safety copy not found; it may have been pruned".to_string())
+}
+
+/// Every `.safety` directory under `entries/`, for sweeps and usage totals.
+pub(crate) fn audio_dirs(entries_root: &Path) -> Vec<PathBuf> {
+    let Ok(items) = fs::read_dir(entries_root) else {
+        return Vec::new();
+    };
+    items
+        .flatten()
+        .map(|item| item.path().join("audio"))
+        .filter(|audio_dir| safety_dir(audio_dir).is_dir())
+        .collect()
+}
+
+/// Files in a safety directory, for callers that must touch every copy (encryption changes).
+pub(crate) fn copy_paths(audio_dir: &Path) -> Vec<PathBuf> {
+    copy_files(audio_dir).into_iter().map(|(_, path)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_are_capped_listed_newest_first_and_found_by_id() {
+        let root = std::env::temp_dir().join(format!("audio-safety-{}", std::process::id()));
+        let audio_dir = root.join("entry-1").join("audio");
+        fs::create_dir_all(&audio_dir).unwrap();
+        let source = audio_dir.join("recording.wav");
+        fs::write(&source, b"RIFF original").unwrap();
+
+        assert_eq!(preserve(&audio_dir, &source, "trim", 0).unwrap(), None);
+        assert_eq!(preserve(&audio_dir, &audio_dir.join("missing.wav"), "trim", 3).unwrap(), None);
+        for operation in ["trim", "normalize", "delete_segment", "trim"] {
+            preserve(&audio_dir, &source, operation, 3).unwrap();
+        }
+        // The copy survives the original being replaced.
+        fs::remove_file(&source).unwrap();
+
+        let copies = list(&audio_dir);
+        assert_eq!(copies.len(), 3);
+        assert_eq!(
+            copies.iter().map(|copy| copy.operation.as_str()).collect::<Vec<_>>(),
+            ["trim", "delete_segment", "normalize"]
+        );
+        assert!(copies.iter().all(|copy| copy.size_bytes == 13 && copy.created_at.ends_with("+00:00")));
+        assert_eq!(fs::read(find(&audio_dir, &copies[0].id).unwrap()).unwrap(), b"RIFF original");
+        assert!(find(&audio_dir, "../recording").is_err());
+        assert!(find(&audio_dir, "20000101T000000000Z-trim").is_err());
+
+        assert_eq!(prune(&audio_dir, 1), 2);
+        assert_eq!(list(&audio_dir)[0].id, copies[0].id);
+        assert_eq!(audio_dirs(&root), vec![audio_dir.clone()]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,196 @@
+use log::{info, warn};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const COMMAND_FAILED: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+/// Runs one method against the app; None means the method is not exposed over the socket.
+pub(crate) type Dispatch = dyn Fn(&str, Value) -> Option<Result<Value, String>> + Send + Sync;
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// Compares every byte so response timing does not reveal how much of a guessed token was right.
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Handles one newline-delimited JSON-RPC 2.0 request. The token travels in a top-level `token` member.
+pub(crate) fn handle_request(line: &str, token: &str, dispatch: &Dispatch) -> Value {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return error_response(Value::Null, PARSE_ERROR, &format!("Invalid JSON: {err}")),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return error_response(id, INVALID_REQUEST, "Request needs a `method` string");
+    };
+    if !request
+        .get("token")
+        .and_then(Value::as_str)
+        .is_some_and(|given| token_matches(token, given))
+    {
+        return error_response(id, UNAUTHORIZED, "Missing or invalid token");
+    }
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+    match dispatch(method, params) {
+        None => error_response(id, METHOD_NOT_FOUND, &format!("Unknown method `{method}`")),
+        Some(Err(message)) => error_response(id, COMMAND_FAILED, &message),
+        Some(Ok(result)) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    }
+}
+
+/// A listening control socket. Dropping it does not stop the listener; call `stop`.
+pub(crate) struct ControlServer {
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+#[cfg(unix)]
+impl ControlServer {
+    pub(crate) fn start(path: &Path, token: String, dispatch: Arc<Dispatch>) -> Result<Self, String> {
+        use std::os::unix::fs::PermissionsExt;
+        use std::os::unix::net::UnixListener;
+
+        // A socket left behind by a crash would make bind fail.
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|e| format!("Failed to remove stale control socket: {e}"))?;
+        }
+        let listener = UnixListener::bind(path).map_err(|e| format!("Failed to open control socket: {e}"))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict control socket permissions: {e}"))?;
+        info!("Control server listening on {}", path.display());
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        warn!("Control server accept failed: {err}");
+                        continue;
+                    }
+                };
+                let token = token.clone();
+                let dispatch = Arc::clone(&dispatch);
+                thread::spawn(move || {
+                    let Ok(reader) = stream.try_clone() else {
+                        return;
+                    };
+                    let mut writer = stream;
+                    for line in BufReader::new(reader).lines() {
+                        let Ok(line) = line else {
+                            break;
+                        };
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        let response = handle_request(&line, &token, dispatch.as_ref());
+                        if writeln!(writer, "{response}").is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            path: path.to_path_buf(),
+            stop,
+            handle,
+        })
+    }
+
+    pub(crate) fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the blocking accept so the listener thread sees the flag.
+        let _ = std::os::unix::net::UnixStream::connect(&self.path);
+        let _ = self.handle.join();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(not(unix))]
+impl ControlServer {
+    pub(crate) fn start(_path: &Path, _token: String, _dispatch: Arc<Dispatch>) -> Result<Self, String> {
+        Err("The control server needs Unix domain sockets and is not available on this platform yet".to_string())
+    }
+
+    pub(crate) fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(method: &str, params: Value) -> Option<Result<Value, String>> {
+        match method {
+            "echo" => Some(Ok(params)),
+            "fail" => Some(Err("Entry not found".to_string())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn requests_need_the_token_and_a_known_method() {
+        let call = |line: &str| handle_request(line, "secret", &echo);
+
+        let ok = call(r#"{"jsonrpc":"2.0","id":1,"method":"echo","params":{"a":1},"token":"secret"}"#);
+        assert_eq!(ok["id"], 1);
+        assert_eq!(ok["result"]["a"], 1);
+
+        assert_eq!(call(r#"{"id":2,"method":"echo","token":"secreT"}"#)["error"]["code"], UNAUTHORIZED);
+        assert_eq!(call(r#"{"id":3,"method":"echo"}"#)["error"]["code"], UNAUTHORIZED);
+        assert_eq!(call(r#"{"id":4,"method":"nope","token":"secret"}"#)["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(call(r#"{"id":5,"token":"secret"}"#)["error"]["code"], INVALID_REQUEST);
+        assert_eq!(call("not json")["error"]["code"], PARSE_ERROR);
+
+        let failed = call(r#"{"id":6,"method":"fail","token":"secret"}"#);
+        assert_eq!(failed["error"]["code"], COMMAND_FAILED);
+        assert_eq!(failed["error"]["message"], "Entry not found");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn example_client_round_trip_over_the_socket() {
+        use std::os::unix::net::UnixStream;
+
+        let path = std::env::temp_dir().join(format!("beyond-call-control-{}.sock", std::process::id()));
+        let server = ControlServer::start(&path, "secret".to_string(), Arc::new(echo)).unwrap();
+
+        // The same thing a cron job does with
+        // `echo '{"id":1,"method":"list_entries","params":{},"token":"…"}' | nc -U control.sock`.
+        let mut client = UnixStream::connect(&path).unwrap();
+        writeln!(client, r#"{{"jsonrpc":"2.0","id":1,"method":"echo","params":["hi"],"token":"secret"}}"#).unwrap();
+        let mut line = String::new();
+        BufReader::new(&client).read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["result"][0], "hi");
+
+        drop(client);
+        server.stop();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,149 @@
+const CHUNK_TARGET_CHARS: usize = 1200;
+const SNIPPET_CHARS: usize = 240;
+
+/// A slice of transcript text; `start`/`end` are char offsets into the revision it was cut from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextChunk {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) text: String,
+}
+
+/// Splits on whitespace so chunks never cut a word; each chunk is roughly `CHUNK_TARGET_CHARS` long.
+pub(crate) fn chunk_text(text: &str) -> Vec<TextChunk> {
+    let mut chunks = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+    let mut word_start: Option<usize> = None;
+
+    let mut push_word = |word_start: usize, word_end: usize, start: &mut Option<usize>, end: &mut usize| {
+        if let Some(chunk_start) = *start {
+            if word_end - chunk_start > CHUNK_TARGET_CHARS {
+                chunks.push((chunk_start, *end));
+                *start = Some(word_start);
+            }
+        } else {
+            *start = Some(word_start);
+        }
+        *end = word_end;
+    };
+
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if let Some(begin) = word_start.take() {
+                push_word(begin, offset, &mut start, &mut end);
+            }
+        } else if word_start.is_none() {
+            word_start = Some(offset);
+        }
+        offset += 1;
+    }
+    if let Some(begin) = word_start {
+        push_word(begin, offset, &mut start, &mut end);
+    }
+    if let Some(chunk_start) = start {
+        chunks.push((chunk_start, end));
+    }
+
+    chunks
+        .into_iter()
+        .map(|(start, end)| TextChunk {
+            start,
+            end,
+            text: char_slice(text, start, end),
+        })
+        .collect()
+}
+
+pub(crate) fn char_slice(text: &str, start: usize, end: usize) -> String {
+    text.chars().skip(start).take(end.saturating_sub(start)).collect()
+}
+
+pub(crate) fn snippet(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= SNIPPET_CHARS {
+        return trimmed.to_string();
+    }
+    let head: String = trimmed.chars().take(SNIPPET_CHARS).collect();
+    format!("{}…", head.trim_end())
+}
+
+pub(crate) fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+pub(crate) fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// Returns 0 for mismatched dimensions or zero vectors so stale rows from another model simply rank last.
+pub(crate) fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    if left.len() != right.len() || left.is_empty() {
+        return 0.0;
+    }
+    let mut dot = 0.0f32;
+    let mut left_norm = 0.0f32;
+    let mut right_norm = 0.0f32;
+    for (a, b) in left.iter().zip(right) {
+        dot += a * b;
+        left_norm += a * a;
+        right_norm += b * b;
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+/// Scores every candidate against the query and keeps the best `limit`, highest first.
+pub(crate) fn rank<T>(query: &[f32], candidates: Vec<(T, Vec<f32>)>, limit: usize) -> Vec<(T, f32)> {
+    let mut scored: Vec<(T, f32)> = candidates
+        .into_iter()
+        .map(|(item, vector)| {
+            let score = cosine_similarity(query, &vector);
+            (item, score)
+        })
+        .collect();
+    scored.sort_by(|left, right| right.1.total_cmp(&left.1));
+    scored.truncate(limit);
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_text_keeps_words_whole_and_offsets_exact() {
+        let word = "pricing ";
+        let text = format!("Привет {}", word.repeat(400));
+        let chunks = chunk_text(&text);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert_eq!(char_slice(&text, chunk.start, chunk.end), chunk.text);
+            assert!(!chunk.text.starts_with(' ') && !chunk.text.ends_with(' '));
+            assert!(chunk.text.chars().count() <= CHUNK_TARGET_CHARS);
+        }
+        assert_eq!(chunks[0].start, 0);
+        assert!(chunk_text("   ").is_empty());
+    }
+
+    #[test]
+    fn rank_orders_by_cosine_similarity() {
+        let vector = vec![0.25f32, -1.5, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+
+        let query = [1.0f32, 0.0];
+        let ranked = rank(
+            &query,
+            vec![("orthogonal", vec![0.0, 1.0]), ("same", vec![2.0, 0.0]), ("other-model", vec![1.0, 0.0, 0.0])],
+            2,
+        );
+        assert_eq!(ranked[0], ("same", 1.0));
+        assert_eq!(ranked.len(), 2);
+    }
+}
@@ -0,0 +1,272 @@
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const FILE_MAGIC: &[u8; 8] = b"BCENC01\n";
+const TEXT_PREFIX: &str = "enc:v1:";
+const VERIFIER_PLAINTEXT: &[u8] = b"beyond-call-key-check";
+const SALT_LEN: usize = 16;
+const TEXT_NONCE_LEN: usize = 24;
+// STREAM reserves 5 bytes of the XChaCha nonce for the chunk counter and last-chunk flag.
+const STREAM_NONCE_LEN: usize = 19;
+const STREAM_CHUNK_LEN: usize = 64 * 1024;
+const AEAD_TAG_LEN: usize = 16;
+
+/// Session key derived from the user's passphrase. It is never persisted.
+#[derive(Clone)]
+pub(crate) struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new((&self.0).into())
+    }
+}
+
+impl Drop for EncryptionKey {
+    fn drop(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    let pairs = text.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err("Invalid hex payload".to_string());
+    }
+    pairs
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| "Invalid hex payload".to_string())
+        })
+        .collect()
+}
+
+pub(crate) fn new_salt() -> String {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    to_hex(&salt)
+}
+
+pub(crate) fn derive_key(passphrase: &str, salt_hex: &str) -> Result<EncryptionKey, String> {
+    let salt = from_hex(salt_hex)?;
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Failed to derive encryption key: {e}"))?;
+    Ok(EncryptionKey(key))
+}
+
+fn seal_bytes(key: &EncryptionKey, plaintext: &[u8]) -> Result<String, String> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "Failed to encrypt data".to_string())?;
+    Ok(format!("{}{}", to_hex(&nonce), to_hex(&ciphertext)))
+}
+
+fn open_bytes(key: &EncryptionKey, payload_hex: &str) -> Result<Vec<u8>, String> {
+    let payload = from_hex(payload_hex)?;
+    if payload.len() < TEXT_NONCE_LEN + AEAD_TAG_LEN {
+        return Err("Encrypted payload is truncated".to_string());
+    }
+    let (nonce, ciphertext) = payload.split_at(TEXT_NONCE_LEN);
+    key.cipher()
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Failed to decrypt data: wrong key or corrupted payload".to_string())
+}
+
+/// Produces the value stored in settings so a passphrase can be checked without storing the key.
+pub(crate) fn make_verifier(key: &EncryptionKey) -> Result<String, String> {
+    seal_bytes(key, VERIFIER_PLAINTEXT)
+}
+
+pub(crate) fn verify_key(key: &EncryptionKey, verifier: &str) -> bool {
+    open_bytes(key, verifier)
+        .map(|plaintext| plaintext == VERIFIER_PLAINTEXT)
+        .unwrap_or(false)
+}
+
+pub(crate) fn is_encrypted_text(stored: &str) -> bool {
+    stored.starts_with(TEXT_PREFIX)
+}
+
+pub(crate) fn encrypt_text(key: &EncryptionKey, plaintext: &str) -> Result<String, String> {
+    Ok(format!("{TEXT_PREFIX}{}", seal_bytes(key, plaintext.as_bytes())?))
+}
+
+pub(crate) fn decrypt_text(key: &EncryptionKey, stored: &str) -> Result<String, String> {
+    let payload = stored
+        .strip_prefix(TEXT_PREFIX)
+        .ok_or_else(|| "Value is not encrypted".to_string())?;
+    String::from_utf8(open_bytes(key, payload)?).map_err(|_| "Decrypted text is not valid UTF-8".to_string())
+}
+
+/// Seals binary data, such as an embedding vector, into the same text form `encrypt_text` produces.
+pub(crate) fn encrypt_bytes(key: &EncryptionKey, plaintext: &[u8]) -> Result<String, String> {
+    Ok(format!("{TEXT_PREFIX}{}", seal_bytes(key, plaintext)?))
+}
+
+pub(crate) fn decrypt_bytes(key: &EncryptionKey, stored: &str) -> Result<Vec<u8>, String> {
+    let payload = stored
+        .strip_prefix(TEXT_PREFIX)
+        .ok_or_else(|| "Value is not encrypted".to_string())?;
+    open_bytes(key, payload)
+}
+
+pub(crate) fn is_encrypted_file(path: &Path) -> bool {
+    let mut header = [0u8; FILE_MAGIC.len()];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|_| &header == FILE_MAGIC)
+        .unwrap_or(false)
+}
+
+fn read_chunk(reader: &mut impl Read, len: usize) -> Result<Vec<u8>, String> {
+    let mut buffer = Vec::with_capacity(len);
+    reader
+        .take(len as u64)
+        .read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read file chunk: {e}"))?;
+    Ok(buffer)
+}
+
+/// Encrypts `source` into `target` as a header followed by STREAM-framed 64 KiB chunks.
+pub(crate) fn encrypt_file(key: &EncryptionKey, source: &Path, target: &Path) -> Result<(), String> {
+    let mut reader = BufReader::new(File::open(source).map_err(|e| format!("Failed to open file for encryption: {e}"))?);
+    let mut writer =
+        BufWriter::new(File::create(target).map_err(|e| format!("Failed to create encrypted file: {e}"))?);
+
+    let mut nonce = [0u8; STREAM_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    writer
+        .write_all(FILE_MAGIC)
+        .and_then(|_| writer.write_all(&nonce))
+        .map_err(|e| format!("Failed to write encrypted file header: {e}"))?;
+
+    let mut encryptor = EncryptorBE32::from_aead(key.cipher(), (&nonce).into());
+    let mut current = read_chunk(&mut reader, STREAM_CHUNK_LEN)?;
+    loop {
+        let next = read_chunk(&mut reader, STREAM_CHUNK_LEN)?;
+        if next.is_empty() {
+            let sealed = encryptor
+                .encrypt_last(current.as_slice())
+                .map_err(|_| "Failed to encrypt final file chunk".to_string())?;
+            writer
+                .write_all(&sealed)
+                .map_err(|e| format!("Failed to write encrypted file: {e}"))?;
+            break;
+        }
+        let sealed = encryptor
+            .encrypt_next(current.as_slice())
+            .map_err(|_| "Failed to encrypt file chunk".to_string())?;
+        writer
+            .write_all(&sealed)
+            .map_err(|e| format!("Failed to write encrypted file: {e}"))?;
+        current = next;
+    }
+
+    writer.flush().map_err(|e| format!("Failed to flush encrypted file: {e}"))
+}
+
+pub(crate) fn decrypt_file(key: &EncryptionKey, source: &Path, target: &Path) -> Result<(), String> {
+    let mut reader = BufReader::new(File::open(source).map_err(|e| format!("Failed to open encrypted file: {e}"))?);
+    let mut header = [0u8; FILE_MAGIC.len() + STREAM_NONCE_LEN];
+    reader
+        .read_exact(&mut header)
+        .map_err(|e| format!("Failed to read encrypted file header: {e}"))?;
+    if &header[..FILE_MAGIC.len()] != FILE_MAGIC {
+        return Err("File is not encrypted".to_string());
+    }
+    let nonce: [u8; STREAM_NONCE_LEN] = header[FILE_MAGIC.len()..]
+        .try_into()
+        .map_err(|_| "Invalid encrypted file header".to_string())?;
+
+    let mut writer =
+        BufWriter::new(File::create(target).map_err(|e| format!("Failed to create decrypted file: {e}"))?);
+    let mut decryptor = DecryptorBE32::from_aead(key.cipher(), (&nonce).into());
+    let sealed_len = STREAM_CHUNK_LEN + AEAD_TAG_LEN;
+    let mut current = read_chunk(&mut reader, sealed_len)?;
+    loop {
+        let next = read_chunk(&mut reader, sealed_len)?;
+        if next.is_empty() {
+            let plaintext = decryptor
+                .decrypt_last(current.as_slice())
+                .map_err(|_| "Failed to decrypt file: wrong key or corrupted data".to_string())?;
+            writer
+                .write_all(&plaintext)
+                .map_err(|e| format!("Failed to write decrypted file: {e}"))?;
+            break;
+        }
+        let plaintext = decryptor
+            .decrypt_next(current.as_slice())
+            .map_err(|_| "Failed to decrypt file: wrong key or corrupted data".to_string())?;
+        writer
+            .write_all(&plaintext)
+            .map_err(|e| format!("Failed to write decrypted file: {e}"))?;
+        current = next;
+    }
+
+    writer.flush().map_err(|e| format!("Failed to flush decrypted file: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn test_key() -> EncryptionKey {
+        derive_key("correct horse", &to_hex(&[7u8; SALT_LEN])).unwrap()
+    }
+
+    #[test]
+    fn text_round_trips_and_verifier_rejects_wrong_passphrase() {
+        let key = test_key();
+        let sealed = encrypt_text(&key, "Привет, call notes").unwrap();
+        assert!(is_encrypted_text(&sealed));
+        assert_eq!(decrypt_text(&key, &sealed).unwrap(), "Привет, call notes");
+
+        let verifier = make_verifier(&key).unwrap();
+        assert!(verify_key(&key, &verifier));
+        let wrong = derive_key("wrong horse", &to_hex(&[7u8; SALT_LEN])).unwrap();
+        assert!(!verify_key(&wrong, &verifier));
+        assert!(decrypt_text(&wrong, &sealed).is_err());
+
+        let bytes = [0u8, 159, 146, 150, 255];
+        let sealed = encrypt_bytes(&key, &bytes).unwrap();
+        assert!(is_encrypted_text(&sealed));
+        assert_eq!(decrypt_bytes(&key, &sealed).unwrap(), bytes);
+        assert!(decrypt_bytes(&wrong, &sealed).is_err());
+    }
+
+    #[test]
+    fn file_round_trips_across_chunk_boundaries() {
+        let dir = std::env::temp_dir().join(format!("encryption-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let plain = dir.join("plain.wav");
+        let sealed = dir.join("sealed.wav");
+        let restored = dir.join("restored.wav");
+        let data: Vec<u8> = (0..(STREAM_CHUNK_LEN * 2 + 123)).map(|index| (index % 251) as u8).collect();
+        fs::write(&plain, &data).unwrap();
+
+        let key = test_key();
+        encrypt_file(&key, &plain, &sealed).unwrap();
+        assert!(is_encrypted_file(&sealed));
+        assert!(!is_encrypted_file(&plain));
+        decrypt_file(&key, &sealed, &restored).unwrap();
+        assert_eq!(fs::read(&restored).unwrap(), data);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
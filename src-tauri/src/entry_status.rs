@@ -0,0 +1,137 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+
+/// Where an entry is in its life cycle, stored in `entries.status` as the lowercase name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EntryStatus {
+    New,
+    Recording,
+    Recorded,
+    Transcribed,
+    Edited,
+    Processed,
+}
+
+impl EntryStatus {
+    pub(crate) const ALL: [EntryStatus; 6] = [
+        EntryStatus::New,
+        EntryStatus::Recording,
+        EntryStatus::Recorded,
+        EntryStatus::Transcribed,
+        EntryStatus::Edited,
+        EntryStatus::Processed,
+    ];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            EntryStatus::New => "new",
+            EntryStatus::Recording => "recording",
+            EntryStatus::Recorded => "recorded",
+            EntryStatus::Transcribed => "transcribed",
+            EntryStatus::Edited => "edited",
+            EntryStatus::Processed => "processed",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+impl fmt::Display for EntryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromSql for EntryStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        Self::parse(text).ok_or_else(|| FromSqlError::Other(format!("Unknown entry status `{text}`").into()))
+    }
+}
+
+impl ToSql for EntryStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+/// Checks one status change. Only a finished recording leaves `recording`, and only for `recorded`; the
+/// startup recovery pass (`recovery`) may also settle an interrupted recording on whatever its content
+/// supports. Starting a recording is allowed from any other state, since a new segment can be appended
+/// to an entry at any point, and the processing states may follow each other in any order because
+/// transcripts and artifacts can be regenerated or edited independently.
+pub(crate) fn check_transition(from: EntryStatus, to: EntryStatus, recovery: bool) -> Result<(), String> {
+    let allowed = match (from, to) {
+        (EntryStatus::Recording, EntryStatus::Recorded) => true,
+        (EntryStatus::Recording, EntryStatus::Recording) => false,
+        (EntryStatus::Recording, _) => recovery,
+        (_, EntryStatus::Recorded) => false,
+        _ => true,
+    };
+    if allowed {
+        return Ok(());
+    }
+    let message = if from == EntryStatus::Recording && to == EntryStatus::Recording {
+        "The entry is already recording".to_string()
+    } else if from == EntryStatus::Recording {
+        format!("The entry is still recording; stop the recording before it can become `{to}`")
+    } else {
+        format!("An entry cannot go from `{from}` to `{to}`")
+    };
+    Err(json!({
+        "code": "INVALID_STATUS_TRANSITION",
+        "message": message,
+        "from": from,
+        "to": to,
+    })
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_round_trip_through_their_stored_names() {
+        for status in EntryStatus::ALL {
+            assert_eq!(EntryStatus::parse(status.as_str()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
+            let parsed: EntryStatus = serde_json::from_value(json!(status.as_str())).unwrap();
+            assert_eq!(parsed, status);
+        }
+        assert_eq!(EntryStatus::parse("Processed"), None);
+        assert!(serde_json::from_value::<EntryStatus>(json!("archived")).is_err());
+    }
+
+    #[test]
+    fn recording_is_only_left_by_stopping_or_recovery() {
+        use EntryStatus::*;
+
+        for from in EntryStatus::ALL {
+            for to in EntryStatus::ALL {
+                let allowed = check_transition(from, to, false).is_ok();
+                let expected = match (from, to) {
+                    (Recording, to) => to == Recorded,
+                    (_, Recorded) => false,
+                    _ => true,
+                };
+                assert_eq!(allowed, expected, "{from} -> {to}");
+            }
+        }
+
+        assert!(check_transition(Recording, New, true).is_ok());
+        assert!(check_transition(Recording, Processed, true).is_ok());
+        assert!(check_transition(Recording, Recording, true).is_err());
+        assert!(check_transition(Processed, Recorded, true).is_err());
+
+        let error: serde_json::Value =
+            serde_json::from_str(&check_transition(Recording, Transcribed, false).unwrap_err()).unwrap();
+        assert_eq!(error["code"], "INVALID_STATUS_TRANSITION");
+        assert_eq!((error["from"].as_str(), error["to"].as_str()), (Some("recording"), Some("transcribed")));
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+/// Artifact sections of `entry.md`, in the order they are written.
+pub(crate) const ARTIFACT_SECTIONS: [(&str, &str); 5] = [
+    ("Summary", "summary"),
+    ("Analysis", "analysis"),
+    ("Critique (Recruitment Head)", "critique_recruitment"),
+    ("Critique (Sales Head)", "critique_sales"),
+    ("Critique (Customer Success Lead)", "critique_cs"),
+];
+
+const TRANSCRIPT_HEADING: &str = "Transcript";
+const DYNAMICS_HEADING: &str = "Call Dynamics";
+// Written in place of a section's text when there was nothing to export.
+const EMPTY_SECTION: &str = "(none)";
+
+/// An artifact read back from its `entry.md` section.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExportedArtifact {
+    pub(crate) artifact_type: &'static str,
+    pub(crate) text: String,
+    pub(crate) output_language: Option<String>,
+    /// The section was a fenced JSON block.
+    pub(crate) is_json: bool,
+    /// Set when the export noted the artifact was built from an older transcript than the one exported.
+    pub(crate) stale: bool,
+}
+
+/// What an `entry.md` written by this app says about its entry. Old exports may lack the front matter, the
+/// timestamps, or some sections; those come back empty.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExportedEntry {
+    pub(crate) title: String,
+    pub(crate) entry_id: String,
+    pub(crate) created_at: Option<String>,
+    pub(crate) updated_at: Option<String>,
+    pub(crate) metadata: BTreeMap<String, String>,
+    pub(crate) transcript: Option<String>,
+    pub(crate) transcript_language: Option<String>,
+    pub(crate) artifacts: Vec<ExportedArtifact>,
+}
+
+/// Splits `---` front matter of `key: <JSON scalar>` lines from the text after it. Values that are not JSON are
+/// kept as written.
+pub(crate) fn split_front_matter(text: &str) -> (BTreeMap<String, serde_json::Value>, &str) {
+    let mut fields = BTreeMap::new();
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return (fields, text);
+    };
+    let Some((block, body)) = rest.split_once("\n---\n") else {
+        return (fields, text);
+    };
+    for line in block.lines() {
+        if let Some((key, value)) = line.split_once(": ") {
+            let value = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            fields.insert(key.trim().to_string(), value);
+        }
+    }
+    (fields, body.strip_prefix('\n').unwrap_or(body))
+}
+
+/// Finds `## <heading>` on its own line at or after `from`, allowing a ` (<language>)` suffix. Returns where the
+/// heading line starts, where its section text starts, and the suffix.
+fn find_heading<'a>(text: &'a str, heading: &str, from: usize) -> Option<(usize, usize, Option<&'a str>)> {
+    let marker = format!("## {heading}");
+    let mut search = from;
+    while let Some(found) = text[search..].find(&marker) {
+        let start = search + found;
+        let line_end = text[start..].find('\n').map_or(text.len(), |end| start + end);
+        let suffix = text[start + marker.len()..line_end].trim_end_matches('\r');
+        let language = suffix.strip_prefix(" (").and_then(|rest| rest.strip_suffix(')'));
+        let at_line_start = start == 0 || text[..start].ends_with('\n');
+        if at_line_start && (suffix.is_empty() || language.is_some()) {
+            return Some((start, (line_end + 1).min(text.len()), language));
+        }
+        search = start + marker.len();
+    }
+    None
+}
+
+fn line_value<'a>(header: &'a str, label: &str) -> Option<&'a str> {
+    header.lines().find_map(|line| line.strip_prefix(label)).map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Reads an `entry.md` produced by this app. Sections are matched by their fixed headings in the order they are
+/// written, so headings inside an artifact's own text are left alone.
+pub(crate) fn parse_entry_markdown(markdown: &str) -> Result<ExportedEntry, String> {
+    let markdown = markdown.trim_start_matches('\u{feff}');
+    let (front_matter, body) = split_front_matter(markdown);
+    let not_ours = || "entry.md was not written by this app".to_string();
+    let title_line = body.lines().find(|line| !line.trim().is_empty()).ok_or_else(not_ours)?;
+    let title = title_line.strip_prefix("# ").map(str::trim).filter(|title| !title.is_empty()).ok_or_else(not_ours)?;
+    let (transcript_start, transcript_text_start, _) =
+        find_heading(body, TRANSCRIPT_HEADING, 0).ok_or_else(|| "entry.md has no transcript section".to_string())?;
+    let header = &body[..transcript_start];
+    let entry_id =
+        line_value(header, "- Entry ID:").map(|value| value.trim_matches('`').to_string()).ok_or_else(not_ours)?;
+
+    // Each heading is looked for after the previous one; sections missing from older layouts are skipped.
+    let mut sections: Vec<(Option<&'static str>, usize, usize, Option<&str>)> =
+        vec![(None, transcript_start, transcript_text_start, None)];
+    let mut cursor = transcript_text_start;
+    for (heading, artifact_type) in ARTIFACT_SECTIONS {
+        if let Some((start, text_start, language)) = find_heading(body, heading, cursor) {
+            sections.push((Some(artifact_type), start, text_start, language));
+            cursor = text_start;
+        }
+    }
+    let end = find_heading(body, DYNAMICS_HEADING, cursor).map_or(body.len(), |(start, _, _)| start);
+
+    let mut transcript = None;
+    let mut artifacts = Vec::new();
+    for (index, (artifact_type, _, text_start, language)) in sections.iter().enumerate() {
+        let text_end = sections.get(index + 1).map_or(end, |next| next.1);
+        let text = body[*text_start..text_end].trim();
+        if text.is_empty() || text == EMPTY_SECTION {
+            continue;
+        }
+        let Some(artifact_type) = artifact_type else {
+            transcript = Some(text.to_string());
+            continue;
+        };
+        let stale_note = text
+            .strip_prefix("_Generated from transcript version ")
+            .and_then(|rest| rest.split_once("._\n"))
+            .map(|(_, rest)| rest.trim_start());
+        let text = stale_note.unwrap_or(text);
+        let json = text.strip_prefix("```json\n").and_then(|rest| rest.strip_suffix("```"));
+        artifacts.push(ExportedArtifact {
+            artifact_type,
+            text: json.map_or(text, str::trim_end).to_string(),
+            output_language: language.map(str::to_string),
+            is_json: json.is_some(),
+            stale: stale_note.is_some(),
+        });
+    }
+
+    let metadata = front_matter
+        .into_iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(text) => (key, text),
+            other => (key, other.to_string()),
+        })
+        .collect();
+    Ok(ExportedEntry {
+        title: title.to_string(),
+        entry_id,
+        created_at: line_value(header, "- Created:").map(str::to_string),
+        updated_at: line_value(header, "- Updated:").map(str::to_string),
+        metadata,
+        transcript,
+        transcript_language: line_value(header, "- Transcript Language:").map(str::to_string),
+        artifacts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_layout_round_trips_sections_languages_and_notes() {
+        let markdown = "---\ncustomer: \"Acme, \\\"EU\\\"\"\ndeal_size: \"12k\"\n---\n\n# Renewal call\n\n\
+- Entry ID: `e-1`\n- Created: 2026-01-02T10:00:00+00:00\n- Updated: 2026-01-03T10:00:00+00:00\n\
+- Transcript Version: 3\n- Transcript Language: en\n\n## Transcript\n\nHello.\n\n## Not a section\n\nStill the transcript.\n\n\
+## Summary (de)\n\n## Ergebnis\n\n- Verlängert\n\n## Analysis\n\n\
+_Generated from transcript version 2; the transcript or recording has changed since._\n\nRisky.\n\n\
+## Critique (Recruitment Head)\n\n(none)\n\n## Critique (Sales Head)\n\n```json\n{\n  \"score\": 4\n}\n```\n\n\
+## Critique (Customer Success Lead)\n\n(none)\n\n## Call Dynamics\n\n| Window | Talk |\n";
+        let entry = parse_entry_markdown(markdown).unwrap();
+        assert_eq!((entry.title.as_str(), entry.entry_id.as_str()), ("Renewal call", "e-1"));
+        assert_eq!(entry.created_at.as_deref(), Some("2026-01-02T10:00:00+00:00"));
+        assert_eq!(entry.metadata["customer"], "Acme, \"EU\"");
+        assert_eq!(entry.transcript.as_deref(), Some("Hello.\n\n## Not a section\n\nStill the transcript."));
+        assert_eq!(entry.transcript_language.as_deref(), Some("en"));
+        assert_eq!(
+            entry.artifacts,
+            [
+                ExportedArtifact {
+                    artifact_type: "summary",
+                    text: "## Ergebnis\n\n- Verlängert".to_string(),
+                    output_language: Some("de".to_string()),
+                    is_json: false,
+                    stale: false,
+                },
+                ExportedArtifact {
+                    artifact_type: "analysis",
+                    text: "Risky.".to_string(),
+                    output_language: None,
+                    is_json: false,
+                    stale: true,
+                },
+                ExportedArtifact {
+                    artifact_type: "critique_sales",
+                    text: "{\n  \"score\": 4\n}".to_string(),
+                    output_language: None,
+                    is_json: true,
+                    stale: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn old_layouts_import_what_they_have_and_foreign_files_are_rejected() {
+        let old = "# Intro\n\n- Entry ID: `old-1`\n\n## Transcript\n\nHi there\n\n## Summary\n\nShort.\n";
+        let entry = parse_entry_markdown(old).unwrap();
+        assert_eq!((entry.created_at, entry.metadata.len()), (None, 0));
+        assert_eq!((entry.transcript.as_deref(), entry.transcript_language), (Some("Hi there"), None));
+        assert_eq!(entry.artifacts.len(), 1);
+        assert_eq!(entry.artifacts[0].text, "Short.");
+
+        let empty = parse_entry_markdown("# T\n\n- Entry ID: `x`\n\n## Transcript\n\n(none)\n").unwrap();
+        assert_eq!((empty.transcript, empty.artifacts.len()), (None, 0));
+
+        assert!(parse_entry_markdown("# Meeting notes\n\nJust notes.\n").is_err());
+        assert!(parse_entry_markdown("Some text\n\n## Transcript\n\nx\n").is_err());
+        assert!(parse_entry_markdown("# Notes\n\n## Transcript\n\nNo entry id\n").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One file in an entry's `exports` directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ExportFile {
+    pub(crate) filename: String,
+    pub(crate) size_bytes: u64,
+    pub(crate) created_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ExportUsage {
+    pub(crate) entry_id: String,
+    pub(crate) files: usize,
+    pub(crate) bytes: u64,
+}
+
+fn export_files(dir: &Path) -> Vec<(SystemTime, ExportFile)> {
+    let Ok(items) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(SystemTime, ExportFile)> = items
+        .flatten()
+        .filter_map(|item| {
+            let meta = item.metadata().ok().filter(|meta| meta.is_file())?;
+            // Not every filesystem records creation times.
+            let created = meta.created().or_else(|_| meta.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+            let file = ExportFile {
+                filename: item.file_name().to_str()?.to_string(),
+                size_bytes: meta.len(),
+                created_at: DateTime::<Utc>::from(created).to_rfc3339(),
+            };
+            Some((created, file))
+        })
+        .collect();
+    files.sort_by(|(a_time, a), (b_time, b)| b_time.cmp(a_time).then_with(|| b.filename.cmp(&a.filename)));
+    files
+}
+
+/// Newest first.
+pub(crate) fn list(dir: &Path) -> Vec<ExportFile> {
+    export_files(dir).into_iter().map(|(_, file)| file).collect()
+}
+
+/// Resolves `filename` to a file directly inside `dir`. Separators, `..` and links that lead
+/// elsewhere are rejected, so a caller can never reach outside the exports directory.
+pub(crate) fn resolve(dir: &Path, filename: &str) -> Result<PathBuf, String> {
+    let plain_name = !filename.is_empty()
+        && filename != "."
+        && filename != ".."
+        && !filename.contains(['/', '\\'])
+        && Path::new(filename).file_name().is_some_and(|name| name == filename);
+    if !plain_name {
+        return Err(format!("Invalid export file name `{filename}`"));
+    }
+    let not_found = || format!("Export `{filename}` not found");
+    let root = dir.canonicalize().map_err(|_| not_found())?;
+    let path = root.join(filename).canonicalize().map_err(|_| not_found())?;
+    if path.parent() != Some(root.as_path()) || !path.is_file() {
+        return Err(not_found());
+    }
+    Ok(path)
+}
+
+/// Deletes all but the `keep` newest exports and returns how many went. `keep == 0` keeps everything.
+pub(crate) fn prune(dir: &Path, keep: usize) -> usize {
+    if keep == 0 {
+        return 0;
+    }
+    export_files(dir)
+        .into_iter()
+        .skip(keep)
+        .filter(|(_, file)| fs::remove_file(dir.join(&file.filename)).is_ok())
+        .count()
+}
+
+/// Export totals for every entry under `entries/` that has any.
+pub(crate) fn usage(entries_root: &Path) -> Vec<ExportUsage> {
+    let Ok(items) = fs::read_dir(entries_root) else {
+        return Vec::new();
+    };
+    let mut usage: Vec<ExportUsage> = items
+        .flatten()
+        .filter_map(|item| {
+            let entry_id = item.file_name().to_str()?.to_string();
+            let files = list(&item.path().join("exports"));
+            (!files.is_empty()).then(|| ExportUsage {
+                entry_id,
+                files: files.len(),
+                bytes: files.iter().map(|file| file.size_bytes).sum(),
+            })
+        })
+        .collect();
+    usage.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.entry_id.cmp(&b.entry_id)));
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_are_listed_pruned_and_resolved_only_inside_the_directory() {
+        let root = std::env::temp_dir().join(format!("entry-exports-{}", std::process::id()));
+        let dir = root.join("entry-1").join("exports");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(root.join("entry-1").join("secret.txt"), b"outside").unwrap();
+        for (index, name) in ["export-1.zip", "export-2.zip", "chapters-3.md"].iter().enumerate() {
+            fs::write(dir.join(name), vec![0u8; 10 * (index + 1)]).unwrap();
+            // Spread creation times so the order does not depend on timer resolution.
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let files = list(&dir);
+        assert_eq!(
+            files.iter().map(|file| file.filename.as_str()).collect::<Vec<_>>(),
+            ["chapters-3.md", "export-2.zip", "export-1.zip"]
+        );
+        assert_eq!(files[0].size_bytes, 30);
+        assert_eq!(usage(&root), vec![ExportUsage { entry_id: "entry-1".to_string(), files: 3, bytes: 60 }]);
+
+        assert_eq!(resolve(&dir, "export-1.zip").unwrap(), dir.canonicalize().unwrap().join("export-1.zip"));
+        for bad in ["", "..", "../secret.txt", "sub/export-1.zip", "..\\secret.txt", "missing.zip"] {
+            assert!(resolve(&dir, bad).is_err(), "{bad:?}");
+        }
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("entry-1").join("secret.txt"), dir.join("link.txt")).unwrap();
+            assert!(resolve(&dir, "link.txt").is_err());
+            fs::remove_file(dir.join("link.txt")).unwrap();
+        }
+
+        assert_eq!(prune(&dir, 0), 0);
+        assert_eq!(prune(&dir, 2), 1);
+        assert!(!dir.join("export-1.zip").exists());
+        assert!(list(&root.join("entry-2").join("exports")).is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,114 @@
+/// Where search terms occur in a text, for highlighting. Ranges are `[start, end)` in chars, not bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Highlights {
+    /// In text order, overlapping hits merged, at most the limit passed to `find_terms`.
+    pub(crate) ranges: Vec<(usize, usize)>,
+    /// Every hit of every term, including those past the limit.
+    pub(crate) total_matches: usize,
+    /// Whether each term was found at least once.
+    pub(crate) all_terms_found: bool,
+}
+
+/// The distinct words of a search query; case is ignored when matching, so duplicates differing only in case go.
+pub(crate) fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split_whitespace() {
+        if !terms.iter().any(|term| fold(term).eq(fold(word))) {
+            terms.push(word.to_string());
+        }
+    }
+    terms
+}
+
+fn fold(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars().flat_map(char::to_lowercase)
+}
+
+/// Finds each term in `text` ignoring case. A character whose lowercase form is longer than itself (such as `İ`)
+/// is matched whole or not at all, so a range never splits a character.
+pub(crate) fn find_terms(text: &str, terms: &[String], limit: usize) -> Highlights {
+    // Each lowercased char, with the index of the char in `text` it came from.
+    let folded: Vec<(char, usize)> =
+        text.chars().enumerate().flat_map(|(index, ch)| ch.to_lowercase().map(move |lower| (lower, index))).collect();
+    let starts_char = |at: usize| at == 0 || folded[at - 1].1 != folded[at].1;
+    let ends_char = |at: usize| at + 1 == folded.len() || folded[at + 1].1 != folded[at].1;
+
+    let mut hits = Vec::new();
+    let mut all_terms_found = true;
+    for term in terms {
+        let needle: Vec<char> = fold(term).collect();
+        let before = hits.len();
+        let mut at = 0;
+        while !needle.is_empty() && at + needle.len() <= folded.len() {
+            let last = at + needle.len() - 1;
+            let found = starts_char(at)
+                && ends_char(last)
+                && folded[at..=last].iter().zip(&needle).all(|((ch, _), wanted)| ch == wanted);
+            if found {
+                hits.push((folded[at].1, folded[last].1 + 1));
+                at = last + 1;
+            } else {
+                at += 1;
+            }
+        }
+        all_terms_found &= hits.len() > before;
+    }
+
+    let total_matches = hits.len();
+    hits.sort_unstable();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in hits {
+        match ranges.last_mut() {
+            Some(previous) if start <= previous.1 => previous.1 = previous.1.max(end),
+            _ => ranges.push((start, end)),
+        }
+        if ranges.len() > limit {
+            ranges.truncate(limit);
+            break;
+        }
+    }
+    Highlights { ranges, total_matches, all_terms_found }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        query_terms(query)
+    }
+
+    #[test]
+    fn ranges_count_chars_and_never_split_one() {
+        let text = "👋 Café, CAFÉ and café — naïve";
+        let found = find_terms(text, &terms("café NAÏVE Café"), 10);
+        assert_eq!(found.ranges, [(2, 6), (8, 12), (17, 21), (24, 29)]);
+        assert_eq!(found.total_matches, 4);
+        assert!(found.all_terms_found);
+        let chars: Vec<char> = text.chars().collect();
+        assert_eq!(chars[24..29].iter().collect::<String>(), "naïve");
+
+        // `İ` lowercases to `i` plus a combining dot: the whole letter matches, its first half does not.
+        let turkish = "İstanbul, istanbul";
+        assert_eq!(find_terms(turkish, &terms("i\u{307}stanbul"), 10).ranges, [(0, 8)]);
+        let found = find_terms(turkish, &terms("istanbul"), 10);
+        assert_eq!(found.ranges, [(10, 18)]);
+        assert!(!find_terms("İx", &terms("i"), 10).all_terms_found);
+    }
+
+    #[test]
+    fn overlapping_hits_merge_and_the_limit_keeps_the_total() {
+        let found = find_terms("budget budgets budget", &terms("budget gets"), 10);
+        assert_eq!(found.ranges, [(0, 6), (7, 14), (15, 21)]);
+        assert_eq!(found.total_matches, 4);
+
+        let found = find_terms("a a a a a", &terms("a"), 2);
+        assert_eq!(found.ranges, [(0, 1), (2, 3)]);
+        assert_eq!(found.total_matches, 5);
+
+        let found = find_terms("Quarterly plan", &terms("plan roadmap"), 10);
+        assert_eq!(found.ranges, [(10, 14)]);
+        assert!(!found.all_terms_found);
+        assert_eq!(terms("  Plan plan PLAN road "), ["Plan", "road"]);
+    }
+}
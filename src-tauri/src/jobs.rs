@@ -0,0 +1,322 @@
+use serde::Serialize;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KindPolicy {
+    /// How many jobs of the kind may run at once; never less than one.
+    pub(crate) limit: usize,
+    /// CPU-heavy kinds hold back while a recording is live so the capture keeps its share of the machine.
+    pub(crate) yields_to_recording: bool,
+}
+
+/// Why a job cannot start right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Blocked {
+    Paused,
+    Recording,
+    Busy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct KindSnapshot<K> {
+    pub(crate) kind: K,
+    pub(crate) limit: usize,
+    pub(crate) running: usize,
+    pub(crate) waiting: usize,
+    /// Set when a new job of this kind would not start immediately.
+    pub(crate) blocked: Option<Blocked>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Snapshot<K> {
+    pub(crate) paused: bool,
+    pub(crate) recording: bool,
+    pub(crate) kinds: Vec<KindSnapshot<K>>,
+}
+
+struct KindState<K> {
+    kind: K,
+    policy: KindPolicy,
+    running: usize,
+    // Tickets of the jobs waiting for this kind, oldest first.
+    queue: Vec<u64>,
+}
+
+struct State<K> {
+    kinds: Vec<KindState<K>>,
+    paused: bool,
+    recording: bool,
+    next_ticket: u64,
+}
+
+impl<K: Copy + PartialEq> State<K> {
+    fn kind(&mut self, kind: K) -> &mut KindState<K> {
+        match self.kinds.iter().position(|state| state.kind == kind) {
+            Some(index) => &mut self.kinds[index],
+            None => {
+                let policy = KindPolicy { limit: 1, yields_to_recording: false };
+                self.kinds.push(KindState { kind, policy, running: 0, queue: Vec::new() });
+                self.kinds.last_mut().expect("kind was just added")
+            }
+        }
+    }
+
+    /// What stops a job from starting; `ticket` is the caller's place in line, if it has one.
+    fn blocked(&mut self, kind: K, ticket: Option<u64>) -> Option<Blocked> {
+        let (paused, recording) = (self.paused, self.recording);
+        let state = self.kind(kind);
+        if paused {
+            Some(Blocked::Paused)
+        } else if recording && state.policy.yields_to_recording {
+            Some(Blocked::Recording)
+        } else if state.running >= state.policy.limit || state.queue.first().is_some_and(|first| Some(*first) != ticket)
+        {
+            Some(Blocked::Busy)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decides when background work may run: each kind has its own limit, waiting jobs start in the order
+/// they asked, `pause` holds back everything not yet started, and kinds marked `yields_to_recording`
+/// wait while a recording is live. Running jobs are never interrupted.
+pub(crate) struct Scheduler<K> {
+    state: Mutex<State<K>>,
+    changed: Condvar,
+}
+
+/// A running job's slot, given back when dropped.
+pub(crate) struct Permit<K: Copy + PartialEq> {
+    scheduler: Arc<Scheduler<K>>,
+    kind: K,
+}
+
+impl<K: Copy + PartialEq> Drop for Permit<K> {
+    fn drop(&mut self) {
+        let mut state = self.scheduler.lock();
+        let kind = state.kind(self.kind);
+        kind.running = kind.running.saturating_sub(1);
+        drop(state);
+        self.scheduler.changed.notify_all();
+    }
+}
+
+impl<K: Copy + PartialEq> Scheduler<K> {
+    pub(crate) fn new(policies: impl IntoIterator<Item = (K, KindPolicy)>) -> Arc<Self> {
+        let kinds = policies
+            .into_iter()
+            .map(|(kind, policy)| KindState {
+                kind,
+                policy: KindPolicy { limit: policy.limit.max(1), ..policy },
+                running: 0,
+                queue: Vec::new(),
+            })
+            .collect();
+        Arc::new(Self {
+            state: Mutex::new(State { kinds, paused: false, recording: false, next_ticket: 0 }),
+            changed: Condvar::new(),
+        })
+    }
+
+    // A panic while holding the lock cannot leave the counters half-updated, so a poisoned lock is reused.
+    fn lock(&self) -> MutexGuard<'_, State<K>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Starts a job now or says why it cannot; never jumps ahead of jobs already waiting.
+    pub(crate) fn try_start(self: &Arc<Self>, kind: K) -> Result<Permit<K>, Blocked> {
+        let mut state = self.lock();
+        if let Some(blocked) = state.blocked(kind, None) {
+            return Err(blocked);
+        }
+        state.kind(kind).running += 1;
+        Ok(Permit { scheduler: Arc::clone(self), kind })
+    }
+
+    /// Waits for the job's turn, then starts it.
+    pub(crate) fn start(self: &Arc<Self>, kind: K) -> Permit<K> {
+        let mut state = self.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.kind(kind).queue.push(ticket);
+        while state.blocked(kind, Some(ticket)).is_some() {
+            state = self.changed.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        let kind_state = state.kind(kind);
+        kind_state.queue.retain(|queued| *queued != ticket);
+        kind_state.running += 1;
+        drop(state);
+        // The next in line may be able to start as well when the limit is above one.
+        self.changed.notify_all();
+        Permit { scheduler: Arc::clone(self), kind }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut State<K>)) {
+        change(&mut self.lock());
+        self.changed.notify_all();
+    }
+
+    pub(crate) fn pause(&self) {
+        self.update(|state| state.paused = true);
+    }
+
+    pub(crate) fn resume(&self) {
+        self.update(|state| state.paused = false);
+    }
+
+    pub(crate) fn set_recording(&self, recording: bool) {
+        self.update(|state| state.recording = recording);
+    }
+
+    pub(crate) fn set_limit(&self, kind: K, limit: usize) {
+        self.update(|state| state.kind(kind).policy.limit = limit.max(1));
+    }
+
+    pub(crate) fn snapshot(&self) -> Snapshot<K> {
+        let mut state = self.lock();
+        let kinds: Vec<K> = state.kinds.iter().map(|kind| kind.kind).collect();
+        let kinds = kinds
+            .into_iter()
+            .map(|kind| {
+                let blocked = state.blocked(kind, None);
+                let kind_state = state.kind(kind);
+                KindSnapshot {
+                    kind,
+                    limit: kind_state.policy.limit,
+                    running: kind_state.running,
+                    waiting: kind_state.queue.len(),
+                    blocked,
+                }
+            })
+            .collect();
+        Snapshot { paused: state.paused, recording: state.recording, kinds }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Fake {
+        Heavy,
+        Light,
+    }
+
+    fn scheduler(heavy_limit: usize, light_limit: usize) -> Arc<Scheduler<Fake>> {
+        Scheduler::new([
+            (Fake::Heavy, KindPolicy { limit: heavy_limit, yields_to_recording: true }),
+            (Fake::Light, KindPolicy { limit: light_limit, yields_to_recording: false }),
+        ])
+    }
+
+    fn waiting(scheduler: &Scheduler<Fake>, kind: Fake) -> usize {
+        scheduler.snapshot().kinds.iter().find(|state| state.kind == kind).map_or(0, |state| state.waiting)
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition not reached");
+            thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    /// Queues one waiting job per label, in order, each recording its label once it starts.
+    fn queue_jobs(
+        scheduler: &Arc<Scheduler<Fake>>,
+        kind: Fake,
+        labels: &[&'static str],
+        started: &Arc<Mutex<Vec<&'static str>>>,
+    ) -> Vec<thread::JoinHandle<()>> {
+        labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                let (job_scheduler, started, label) = (Arc::clone(scheduler), Arc::clone(started), *label);
+                let handle = thread::spawn(move || {
+                    let _permit = job_scheduler.start(kind);
+                    started.lock().unwrap().push(label);
+                    thread::sleep(Duration::from_millis(5));
+                });
+                wait_until(|| waiting(scheduler, kind) == index + 1);
+                handle
+            })
+            .collect()
+    }
+
+    #[test]
+    fn limits_are_per_kind_and_waiting_jobs_start_in_order() {
+        let scheduler = scheduler(1, 2);
+        let heavy = scheduler.try_start(Fake::Heavy).unwrap();
+        assert_eq!(scheduler.try_start(Fake::Heavy).err(), Some(Blocked::Busy));
+        let light = [scheduler.try_start(Fake::Light).unwrap(), scheduler.try_start(Fake::Light).unwrap()];
+        assert_eq!(scheduler.try_start(Fake::Light).err(), Some(Blocked::Busy));
+        drop(light);
+
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let handles = queue_jobs(&scheduler, Fake::Heavy, &["first", "second", "third"], &started);
+        // A free slot elsewhere does not let anyone past the heavy queue.
+        scheduler.try_start(Fake::Light).unwrap();
+        drop(heavy);
+        assert_eq!(scheduler.try_start(Fake::Heavy).err(), Some(Blocked::Busy));
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*started.lock().unwrap(), ["first", "second", "third"]);
+        assert!(scheduler.try_start(Fake::Heavy).is_ok());
+    }
+
+    #[test]
+    fn recording_holds_back_heavy_jobs_until_it_stops() {
+        let scheduler = scheduler(1, 1);
+        scheduler.set_recording(true);
+        assert_eq!(scheduler.try_start(Fake::Heavy).err(), Some(Blocked::Recording));
+        drop(scheduler.try_start(Fake::Light).unwrap());
+
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let handles = queue_jobs(&scheduler, Fake::Heavy, &["transcribe"], &started);
+        thread::sleep(Duration::from_millis(20));
+        assert!(started.lock().unwrap().is_empty());
+        let snapshot = scheduler.snapshot();
+        assert!(snapshot.recording);
+        assert_eq!((snapshot.kinds[0].waiting, snapshot.kinds[0].blocked), (1, Some(Blocked::Recording)));
+
+        scheduler.set_recording(false);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*started.lock().unwrap(), ["transcribe"]);
+    }
+
+    #[test]
+    fn pause_holds_every_kind_and_raised_limits_release_waiters() {
+        let scheduler = scheduler(1, 1);
+        scheduler.pause();
+        assert_eq!(scheduler.try_start(Fake::Light).err(), Some(Blocked::Paused));
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let handles = queue_jobs(&scheduler, Fake::Light, &["export"], &started);
+        thread::sleep(Duration::from_millis(20));
+        assert!(started.lock().unwrap().is_empty());
+        scheduler.resume();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*started.lock().unwrap(), ["export"]);
+
+        let held = scheduler.try_start(Fake::Heavy).unwrap();
+        let handles = queue_jobs(&scheduler, Fake::Heavy, &["a", "b"], &started);
+        scheduler.set_limit(Fake::Heavy, 3);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*started.lock().unwrap(), ["export", "a", "b"]);
+        drop(held);
+        scheduler.set_limit(Fake::Heavy, 0);
+        assert_eq!(scheduler.snapshot().kinds[0].limit, 1);
+    }
+}
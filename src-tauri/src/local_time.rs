@@ -0,0 +1,99 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Real zones run from UTC-12:00 to UTC+14:00; anything outside that is a caller bug.
+pub(crate) const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+pub(crate) fn check_offset(utc_offset_minutes: i32) -> Result<i32, String> {
+    if utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+        return Err(format!("UTC offset must be within ±{MAX_UTC_OFFSET_MINUTES} minutes"));
+    }
+    Ok(utc_offset_minutes)
+}
+
+/// A `YYYY-MM-DD` date in its zero-padded form only, since the result is also compared as a string.
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .filter(|date| date.format("%Y-%m-%d").to_string() == value)
+        .ok_or_else(|| format!("Invalid date '{value}', expected YYYY-MM-DD"))
+}
+
+/// The UTC instant at which local `date` begins, formatted like stored timestamps so it can bound a
+/// string comparison on `created_at`.
+pub(crate) fn day_start_utc(date: NaiveDate, utc_offset_minutes: i32) -> String {
+    let local_midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    let utc = local_midnight - Duration::minutes(i64::from(utc_offset_minutes));
+    DateTime::<Utc>::from_naive_utc_and_offset(utc, Utc).to_rfc3339()
+}
+
+/// `[start, end)` in UTC covering the local days `from` through `to`, both included.
+pub(crate) fn day_range_utc(from: NaiveDate, to: NaiveDate, utc_offset_minutes: i32) -> (String, String) {
+    let after_to = to.succ_opt().unwrap_or(to);
+    (day_start_utc(from, utc_offset_minutes), day_start_utc(after_to, utc_offset_minutes))
+}
+
+/// The same shift as a SQLite date modifier, for grouping rows by local day in SQL.
+pub(crate) fn sqlite_modifier(utc_offset_minutes: i32) -> String {
+    format!("{utc_offset_minutes:+} minutes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    /// The local calendar day a stored UTC timestamp falls on.
+    fn local_date(timestamp: &str, utc_offset_minutes: i32) -> Option<NaiveDate> {
+        let instant = DateTime::parse_from_rfc3339(timestamp).ok()?.with_timezone(&Utc);
+        Some((instant + Duration::minutes(i64::from(utc_offset_minutes))).date_naive())
+    }
+
+    #[test]
+    fn local_days_map_to_utc_bounds_across_offsets() {
+        let day = date("2026-03-29");
+        assert_eq!(day_start_utc(day, 0), "2026-03-29T00:00:00+00:00");
+        assert_eq!(day_start_utc(day, 120), "2026-03-28T22:00:00+00:00");
+        assert_eq!(day_start_utc(day, -300), "2026-03-29T05:00:00+00:00");
+        // Half- and quarter-hour zones: India, Newfoundland, Nepal.
+        assert_eq!(day_start_utc(day, 330), "2026-03-28T18:30:00+00:00");
+        assert_eq!(day_start_utc(day, -210), "2026-03-29T03:30:00+00:00");
+        assert_eq!(day_start_utc(day, 345), "2026-03-28T18:15:00+00:00");
+
+        assert_eq!(
+            day_range_utc(date("2026-12-31"), date("2026-12-31"), 330),
+            ("2026-12-30T18:30:00+00:00".to_string(), "2026-12-31T18:30:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn stored_timestamps_fall_on_the_local_day() {
+        let stamp = "2026-03-28T23:45:00+00:00";
+        assert_eq!(local_date(stamp, 0), Some(date("2026-03-28")));
+        assert_eq!(local_date(stamp, 30), Some(date("2026-03-29")));
+        assert_eq!(local_date(stamp, 330), Some(date("2026-03-29")));
+        assert_eq!(local_date("2026-03-29T03:00:00Z", -210), Some(date("2026-03-28")));
+        assert_eq!(local_date("yesterday", 0), None);
+
+        // Every instant in a local day's range maps back to that day, at the edges too.
+        for offset in [-720, -570, -210, 0, 330, 345, 525, 840] {
+            let day = date("2026-06-15");
+            let (start, end) = day_range_utc(day, day, offset);
+            assert_eq!(local_date(&start, offset), Some(day), "{offset}");
+            let last = DateTime::parse_from_rfc3339(&end).unwrap() - Duration::seconds(1);
+            assert_eq!(local_date(&last.to_rfc3339(), offset), Some(day), "{offset}");
+        }
+    }
+
+    #[test]
+    fn offsets_and_dates_are_checked() {
+        assert_eq!(check_offset(-720), Ok(-720));
+        assert!(check_offset(15 * 60).is_err());
+        assert!(parse_date("2026-3-28").is_err());
+        assert!(parse_date("2026-02-30").is_err());
+        assert_eq!(sqlite_modifier(330), "+330 minutes");
+        assert_eq!(sqlite_modifier(-210), "-210 minutes");
+    }
+}
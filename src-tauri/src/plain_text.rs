@@ -0,0 +1,121 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+/// Markdown as it reads once pasted somewhere that shows it literally: emphasis, heading marks and code
+/// fences go, while paragraphs, list bullets and link targets stay so the text keeps its shape.
+pub(crate) fn from_markdown(markdown: &str) -> String {
+    let mut plain = String::new();
+    // One counter per open list; `None` for bullet lists.
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut link_targets: Vec<String> = Vec::new();
+    let mut link_starts: Vec<usize> = Vec::new();
+
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Item) => {
+                let depth = lists.len().saturating_sub(1);
+                plain.push_str(&"  ".repeat(depth));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        plain.push_str(&format!("{number}. "));
+                        *number += 1;
+                    }
+                    _ => plain.push_str("- "),
+                }
+            }
+            Event::Start(Tag::List(first)) => {
+                end_line(&mut plain);
+                lists.push(first);
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link_targets.push(dest_url.to_string());
+                link_starts.push(plain.len());
+            }
+            Event::End(TagEnd::Link) => {
+                let (Some(target), Some(start)) = (link_targets.pop(), link_starts.pop()) else { continue };
+                // Autolinks already show their target.
+                if plain[start..] != target {
+                    plain.push_str(&format!(" ({target})"));
+                }
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    end_block(&mut plain);
+                }
+            }
+            Event::End(TagEnd::Item) | Event::End(TagEnd::TableRow) | Event::End(TagEnd::TableHead) => {
+                end_line(&mut plain)
+            }
+            Event::End(TagEnd::Paragraph) if !lists.is_empty() => end_line(&mut plain),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::BlockQuote(_) | TagEnd::Table,
+            ) => end_block(&mut plain),
+            Event::End(TagEnd::TableCell) => plain.push('\t'),
+            Event::Text(text) | Event::Code(text) => plain.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => plain.push('\n'),
+            Event::Rule => {
+                end_line(&mut plain);
+                plain.push_str("---");
+                end_block(&mut plain);
+            }
+            Event::TaskListMarker(done) => plain.push_str(if done { "[x] " } else { "[ ] " }),
+            Event::Html(html) | Event::InlineHtml(html) => plain.push_str(&html),
+            _ => {}
+        }
+    }
+
+    let lines: Vec<&str> = plain.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+fn end_line(plain: &mut String) {
+    if !plain.is_empty() && !plain.ends_with('\n') {
+        plain.push('\n');
+    }
+}
+
+fn end_block(plain: &mut String) {
+    end_line(plain);
+    if !plain.is_empty() && !plain.ends_with("\n\n") {
+        plain.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting_goes_but_structure_stays() {
+        let markdown = "## Summary\n\n\
+The **customer** agreed to a _pilot_ with `SSO` enabled.\n\
+See [the deck](https://example.com/deck) or <https://example.com>.\n\n\
+- Next step: ~~pricing~~ legal review\n\
+- Owners:\n  \
+  1. Dana\n  \
+  2. Lee\n\n\
+> Send the recap today.\n\n\
+```\nnot code anymore\n```\n";
+        assert_eq!(
+            from_markdown(markdown),
+            "Summary\n\n\
+The customer agreed to a pilot with SSO enabled.\n\
+See the deck (https://example.com/deck) or https://example.com.\n\n\
+- Next step: pricing legal review\n\
+- Owners:\n  \
+1. Dana\n  \
+2. Lee\n\n\
+Send the recap today.\n\n\
+not code anymore"
+        );
+    }
+
+    #[test]
+    fn tables_become_tab_separated_rows() {
+        let markdown = "| Risk | Owner |\n|---|---|\n| Budget | Dana |\n";
+        assert_eq!(from_markdown(markdown), "Risk\tOwner\nBudget\tDana");
+        assert_eq!(from_markdown(""), "");
+        assert_eq!(from_markdown("Plain text stays as it is."), "Plain text stays as it is.");
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const WORD_START_BONUS: i64 = 8;
+const CONSECUTIVE_BONUS: i64 = 5;
+const LABEL_START_BONUS: i64 = 12;
+const SUBSTRING_BONUS: i64 = 20;
+const GAP_PENALTY: i64 = 1;
+
+#[derive(Debug, Clone)]
+pub(crate) struct QuickItem {
+    pub(crate) kind: &'static str,
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) folder_id: Option<String>,
+    // Lowercased label without whitespace, each char's offset in `label`, and whether it starts a word.
+    chars: Vec<char>,
+    offsets: Vec<usize>,
+    word_starts: Vec<bool>,
+}
+
+impl QuickItem {
+    pub(crate) fn new(kind: &'static str, id: String, label: String, folder_id: Option<String>) -> Self {
+        let mut chars = Vec::new();
+        let mut offsets = Vec::new();
+        let mut word_starts = Vec::new();
+        let mut previous_alphanumeric = false;
+        for (offset, ch) in label.chars().enumerate() {
+            if !ch.is_whitespace() {
+                chars.push(ch.to_lowercase().next().unwrap_or(ch));
+                offsets.push(offset);
+                word_starts.push(!previous_alphanumeric);
+            }
+            previous_alphanumeric = ch.is_alphanumeric();
+        }
+        Self {
+            kind,
+            id,
+            label,
+            folder_id,
+            chars,
+            offsets,
+            word_starts,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct QuickSearchResult {
+    pub(crate) kind: String,
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) folder_id: Option<String>,
+    pub(crate) score: i64,
+    /// Character (not byte) offsets into `label` for highlighting.
+    pub(crate) positions: Vec<usize>,
+}
+
+/// In-memory title index; `counter` is the data change counter it reflects, or None when it must be rebuilt.
+#[derive(Debug, Default)]
+pub(crate) struct QuickIndex {
+    items: HashMap<(&'static str, String), QuickItem>,
+    pub(crate) counter: Option<i64>,
+}
+
+fn score_positions(item: &QuickItem, positions: &[usize]) -> i64 {
+    let mut score = -(item.chars.len() as i64 / 8);
+    for (step, &index) in positions.iter().enumerate() {
+        if index == 0 {
+            score += LABEL_START_BONUS;
+        }
+        if item.word_starts[index] {
+            score += WORD_START_BONUS;
+        }
+        if step > 0 {
+            let previous = positions[step - 1];
+            if index == previous + 1 {
+                score += CONSECUTIVE_BONUS;
+            } else {
+                score -= GAP_PENALTY * (index - previous - 1) as i64;
+            }
+        }
+    }
+    score
+}
+
+/// Scores a lowercased, whitespace-free query against an item; None when it is not a subsequence.
+fn match_item(query: &[char], item: &QuickItem) -> Option<(i64, Vec<usize>)> {
+    let label = &item.chars;
+    if query.is_empty() || query.len() > label.len() {
+        return None;
+    }
+
+    // A contiguous hit beats any scattered subsequence, so prefer the best-placed substring.
+    let substring = (0..=label.len() - query.len())
+        .filter(|&start| label[start..start + query.len()] == *query)
+        .max_by_key(|&start| (item.word_starts[start], std::cmp::Reverse(start)));
+    let (positions, bonus) = match substring {
+        Some(start) => ((start..start + query.len()).collect::<Vec<_>>(), SUBSTRING_BONUS),
+        None => {
+            let mut positions = Vec::with_capacity(query.len());
+            let mut cursor = 0;
+            for ch in query {
+                let offset = label[cursor..].iter().position(|candidate| candidate == ch)?;
+                positions.push(cursor + offset);
+                cursor += offset + 1;
+            }
+            (positions, 0)
+        }
+    };
+    let score = score_positions(item, &positions) + bonus;
+    Some((score, positions.into_iter().map(|index| item.offsets[index]).collect()))
+}
+
+impl QuickIndex {
+    pub(crate) fn from_items(items: Vec<QuickItem>, counter: i64) -> Self {
+        Self {
+            items: items.into_iter().map(|item| ((item.kind, item.id.clone()), item)).collect(),
+            counter: Some(counter),
+        }
+    }
+
+    pub(crate) fn upsert(&mut self, item: QuickItem) {
+        self.items.insert((item.kind, item.id.clone()), item);
+    }
+
+    pub(crate) fn remove(&mut self, kind: &'static str, id: &str) {
+        self.items.remove(&(kind, id.to_string()));
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<QuickSearchResult> {
+        let query: Vec<char> = query
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .map(|ch| ch.to_lowercase().next().unwrap_or(ch))
+            .collect();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(i64, &QuickItem, Vec<usize>)> = self
+            .items
+            .values()
+            .filter_map(|item| match_item(&query, item).map(|(score, positions)| (score, item, positions)))
+            .collect();
+        matches.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then_with(|| a.1.label.len().cmp(&b.1.label.len()))
+                .then_with(|| a.1.label.cmp(&b.1.label))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(score, item, positions)| QuickSearchResult {
+                kind: item.kind.to_string(),
+                id: item.id.clone(),
+                label: item.label.clone(),
+                folder_id: item.folder_id.clone(),
+                score,
+                positions,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> QuickIndex {
+        QuickIndex::from_items(
+            vec![
+                QuickItem::new("entry", "a".into(), "Weekly sync with Acme".into(), Some("f".into())),
+                QuickItem::new("entry", "b".into(), "Quarterly review".into(), Some("f".into())),
+                QuickItem::new("folder", "f".into(), "Customer calls".into(), None),
+            ],
+            7,
+        )
+    }
+
+    #[test]
+    fn prefers_word_prefixes_and_reports_char_positions() {
+        let results = index().search("acme", 10);
+        assert_eq!(results[0].id, "a");
+        assert_eq!(results[0].positions, vec![17, 18, 19, 20]);
+
+        let results = index().search("cc", 10);
+        assert_eq!(results[0].id, "f");
+        assert_eq!(results[0].positions, vec![0, 9]);
+
+        assert!(index().search("zzz", 10).is_empty());
+        assert!(index().search("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn query_spaces_are_ignored_and_updates_apply_in_place() {
+        let mut index = index();
+        let results = index.search("weekly sync", 10);
+        assert_eq!(results[0].positions, vec![0, 1, 2, 3, 4, 5, 7, 8, 9, 10]);
+
+        index.upsert(QuickItem::new("entry", "b".into(), "Роадмап".into(), None));
+        assert_eq!(index.search("роад", 10)[0].id, "b");
+        index.remove("entry", "b");
+        assert!(index.search("роад", 10).is_empty());
+        assert_eq!(index.len(), 2);
+    }
+}
@@ -0,0 +1,232 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RedactionCategory {
+    Email,
+    Phone,
+    Number,
+    Name,
+}
+
+impl RedactionCategory {
+    // Detection priority: earlier categories win when matches overlap.
+    pub(crate) const ALL: [RedactionCategory; 4] = [
+        RedactionCategory::Email,
+        RedactionCategory::Number,
+        RedactionCategory::Phone,
+        RedactionCategory::Name,
+    ];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            RedactionCategory::Email => "email",
+            RedactionCategory::Phone => "phone",
+            RedactionCategory::Number => "number",
+            RedactionCategory::Name => "name",
+        }
+    }
+
+    fn placeholder_prefix(self) -> &'static str {
+        match self {
+            RedactionCategory::Email => "EMAIL",
+            RedactionCategory::Phone => "PHONE",
+            RedactionCategory::Number => "NUMBER",
+            RedactionCategory::Name => "NAME",
+        }
+    }
+}
+
+/// A detected span. `start`/`end` are UTF-16 offsets so the webview can slice the string directly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RedactionMatch {
+    pub(crate) category: RedactionCategory,
+    pub(crate) text: String,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RedactionMapping {
+    pub(crate) placeholder: String,
+    pub(crate) original: String,
+    pub(crate) category: RedactionCategory,
+}
+
+const NAME_STOP_WORDS: &[&str] = &[
+    "The", "This", "That", "These", "Those", "We", "I", "It", "And", "But", "So", "Yes", "No", "Hello", "Hi",
+    "Thanks", "Thank", "Okay", "Ok", "Well", "If", "When", "What", "How", "Why", "Our", "My", "Your",
+];
+
+fn email_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").expect("valid email regex"))
+}
+
+fn long_number_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b\d(?:[ -]?\d){11,18}\b").expect("valid number regex"))
+}
+
+fn phone_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\+?\(?\d[\d ().-]{5,}\d").expect("valid phone regex"))
+}
+
+fn name_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b\p{Lu}\p{Ll}+ \p{Lu}\p{Ll}+\b").expect("valid name regex"))
+}
+
+fn byte_spans(text: &str, category: RedactionCategory) -> Vec<(usize, usize)> {
+    match category {
+        RedactionCategory::Email => email_regex()
+            .find_iter(text)
+            .map(|item| (item.start(), item.end()))
+            .collect(),
+        RedactionCategory::Number => long_number_regex()
+            .find_iter(text)
+            .map(|item| (item.start(), item.end()))
+            .collect(),
+        RedactionCategory::Phone => phone_regex()
+            .find_iter(text)
+            .filter(|item| {
+                let digits = item.as_str().chars().filter(char::is_ascii_digit).count();
+                let before_ok = text[..item.start()]
+                    .chars()
+                    .next_back()
+                    .map(|ch| !ch.is_alphanumeric())
+                    .unwrap_or(true);
+                let after_ok = text[item.end()..]
+                    .chars()
+                    .next()
+                    .map(|ch| !ch.is_alphanumeric())
+                    .unwrap_or(true);
+                (7..=15).contains(&digits) && before_ok && after_ok
+            })
+            .map(|item| (item.start(), item.end()))
+            .collect(),
+        RedactionCategory::Name => name_regex()
+            .find_iter(text)
+            .filter(|item| {
+                let first_word = item.as_str().split(' ').next().unwrap_or("");
+                !NAME_STOP_WORDS.contains(&first_word)
+            })
+            .map(|item| (item.start(), item.end()))
+            .collect(),
+    }
+}
+
+fn detect_byte_spans(text: &str, categories: &[RedactionCategory]) -> Vec<(RedactionCategory, usize, usize)> {
+    let mut spans: Vec<(RedactionCategory, usize, usize)> = Vec::new();
+    for category in RedactionCategory::ALL {
+        if !categories.contains(&category) {
+            continue;
+        }
+        for (start, end) in byte_spans(text, category) {
+            let overlaps = spans
+                .iter()
+                .any(|(_, taken_start, taken_end)| start < *taken_end && *taken_start < end);
+            if !overlaps {
+                spans.push((category, start, end));
+            }
+        }
+    }
+    spans.sort_by_key(|(_, start, _)| *start);
+    spans
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+pub(crate) fn find_redactions(text: &str, categories: &[RedactionCategory]) -> Vec<RedactionMatch> {
+    detect_byte_spans(text, categories)
+        .into_iter()
+        .map(|(category, start, end)| {
+            let start_utf16 = utf16_len(&text[..start]);
+            RedactionMatch {
+                category,
+                text: text[start..end].to_string(),
+                start: start_utf16,
+                end: start_utf16 + utf16_len(&text[start..end]),
+            }
+        })
+        .collect()
+}
+
+/// Replaces matches with typed placeholders like `[EMAIL_1]`; repeated values reuse one placeholder.
+pub(crate) fn redact(text: &str, categories: &[RedactionCategory]) -> (String, Vec<RedactionMapping>) {
+    let mut redacted = String::with_capacity(text.len());
+    let mut mappings: Vec<RedactionMapping> = Vec::new();
+    let mut placeholders: HashMap<(RedactionCategory, String), String> = HashMap::new();
+    let mut counters: HashMap<RedactionCategory, usize> = HashMap::new();
+    let mut cursor = 0;
+
+    for (category, start, end) in detect_byte_spans(text, categories) {
+        let original = &text[start..end];
+        let placeholder = placeholders
+            .entry((category, original.to_string()))
+            .or_insert_with(|| {
+                let counter = counters.entry(category).or_insert(0);
+                *counter += 1;
+                let placeholder = format!("[{}_{}]", category.placeholder_prefix(), counter);
+                mappings.push(RedactionMapping {
+                    placeholder: placeholder.clone(),
+                    original: original.to_string(),
+                    category,
+                });
+                placeholder
+            })
+            .clone();
+        redacted.push_str(&text[cursor..start]);
+        redacted.push_str(&placeholder);
+        cursor = end;
+    }
+    redacted.push_str(&text[cursor..]);
+
+    (redacted, mappings)
+}
+
+pub(crate) fn restore(text: &str, mappings: &[RedactionMapping]) -> String {
+    let mut restored = text.to_string();
+    for mapping in mappings {
+        restored = restored.replace(&mapping.placeholder, &mapping.original);
+    }
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_redactions_reports_utf16_offsets() {
+        let text = "Привет, write to anna.k@example.com";
+        let matches = find_redactions(text, &[RedactionCategory::Email]);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "anna.k@example.com");
+        assert_eq!(matches[0].start, 17);
+        assert_eq!(matches[0].end, 35);
+    }
+
+    #[test]
+    fn redact_prefers_card_numbers_over_phone_and_reuses_placeholders() {
+        let text = "Card 4111 1111 1111 1111, call +1 (415) 555-0100 or +1 (415) 555-0100.";
+        let (redacted, mappings) = redact(text, &RedactionCategory::ALL);
+        assert_eq!(redacted, "Card [NUMBER_1], call [PHONE_1] or [PHONE_1].");
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(restore(&redacted, &mappings), text);
+    }
+
+    #[test]
+    fn name_heuristic_skips_sentence_starters_and_short_numbers() {
+        let text = "The Budget was approved by Maria Lopez in 2024.";
+        let matches = find_redactions(text, &RedactionCategory::ALL);
+        let found: Vec<&str> = matches.iter().map(|item| item.text.as_str()).collect();
+        assert_eq!(found, vec!["Maria Lopez"]);
+    }
+}
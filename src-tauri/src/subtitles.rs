@@ -0,0 +1,216 @@
+/// One timed caption read from an SRT or WebVTT file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Cue {
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) text: String,
+}
+
+/// Why a subtitle file could not be read; `cue` counts cues from 1 in file order, 0 being the WebVTT header.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CueError {
+    pub(crate) cue: usize,
+    pub(crate) message: String,
+}
+
+impl CueError {
+    fn new(cue: usize, message: impl Into<String>) -> Self {
+        Self { cue, message: message.into() }
+    }
+}
+
+/// Reads `hh:mm:ss,mmm` (SRT) or `[hh:]mm:ss.mmm` (WebVTT) into milliseconds.
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (clock, millis) = value.trim().split_once([',', '.'])?;
+    let parts: Vec<&str> = clock.split(':').collect();
+    let (hours, minutes, seconds) = match parts[..] {
+        [hours, minutes, seconds] => (hours, minutes, seconds),
+        [minutes, seconds] => ("0", minutes, seconds),
+        _ => return None,
+    };
+    // Hours may run past two digits; everything else has a fixed width.
+    let number = |part: &str, width: Option<usize>| -> Option<u64> {
+        let valid = !part.is_empty()
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && width.is_none_or(|width| part.len() == width);
+        valid.then(|| part.parse().ok()).flatten()
+    };
+    let (hours, minutes) = (number(hours, None)?, number(minutes, Some(2))?);
+    let (seconds, millis) = (number(seconds, Some(2))?, number(millis, Some(3))?);
+    if minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+/// Reads a `start --> end` line; WebVTT settings after the end time are ignored.
+fn parse_timing(line: &str, cue: usize) -> Result<(u64, u64), CueError> {
+    let (start, rest) = line
+        .split_once("-->")
+        .ok_or_else(|| CueError::new(cue, format!("expected a `start --> end` timing line, found `{line}`")))?;
+    let end = rest.split_whitespace().next().unwrap_or("");
+    let start_ms =
+        parse_timestamp(start).ok_or_else(|| CueError::new(cue, format!("invalid start time `{}`", start.trim())))?;
+    let end_ms = parse_timestamp(end).ok_or_else(|| CueError::new(cue, format!("invalid end time `{end}`")))?;
+    if end_ms < start_ms {
+        return Err(CueError::new(cue, "ends before it starts"));
+    }
+    Ok((start_ms, end_ms))
+}
+
+/// Blocks of non-blank lines, with Windows line endings and a byte order mark tolerated.
+fn blocks(input: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+    for line in input.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+fn cue_text(lines: &[&str], strip_markup: fn(&str) -> String) -> String {
+    lines
+        .iter()
+        .map(|line| strip_markup(line).trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drops the `<i>`/`<b>`/`<font>` styling SRT files commonly carry.
+fn strip_srt_markup(line: &str) -> String {
+    strip_tags(line, |_| None)
+}
+
+/// Drops WebVTT markup, keeping speaker names from `<v Name>` spans as a `Name:` prefix.
+fn strip_vtt_markup(line: &str) -> String {
+    let text = strip_tags(line, |tag| {
+        let name = tag.strip_prefix('v')?.split_once(char::is_whitespace)?.1.trim();
+        (!name.is_empty()).then(|| format!("{name}: "))
+    });
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&nbsp;", " ").replace("&amp;", "&")
+}
+
+fn strip_tags(line: &str, replacement: impl Fn(&str) -> Option<String>) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else { break };
+        text.push_str(&rest[..open]);
+        if let Some(kept) = replacement(&rest[open + 1..open + close]) {
+            text.push_str(&kept);
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+pub(crate) fn parse_srt(input: &str) -> Result<Vec<Cue>, CueError> {
+    let mut cues = Vec::new();
+    for (index, block) in blocks(input).iter().enumerate() {
+        let number = index + 1;
+        // The counter line is optional in practice; the timing line is not.
+        let timing_at = usize::from(!block[0].contains("-->"));
+        let timing = block.get(timing_at).ok_or_else(|| CueError::new(number, "has no timing line"))?;
+        let (start_ms, end_ms) = parse_timing(timing, number)?;
+        let text = cue_text(&block[timing_at + 1..], strip_srt_markup);
+        if !text.is_empty() {
+            cues.push(Cue { start_ms, end_ms, text });
+        }
+    }
+    Ok(cues)
+}
+
+pub(crate) fn parse_vtt(input: &str) -> Result<Vec<Cue>, CueError> {
+    let blocks = blocks(input);
+    let header = blocks.first().and_then(|block| block.first()).copied().unwrap_or("");
+    let valid_header =
+        header.strip_prefix("WEBVTT").is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']));
+    if !valid_header {
+        return Err(CueError::new(0, "the file does not start with `WEBVTT`"));
+    }
+    let mut cues = Vec::new();
+    let mut number = 0;
+    for block in &blocks[1..] {
+        let keyword = block[0].split_whitespace().next().unwrap_or("");
+        if ["NOTE", "STYLE", "REGION"].contains(&keyword) && !block[0].contains("-->") {
+            continue;
+        }
+        number += 1;
+        // A cue may be preceded by an identifier line.
+        let timing_at = usize::from(!block[0].contains("-->"));
+        let timing = block.get(timing_at).ok_or_else(|| CueError::new(number, "has no timing line"))?;
+        let (start_ms, end_ms) = parse_timing(timing, number)?;
+        let text = cue_text(&block[timing_at + 1..], strip_vtt_markup);
+        if !text.is_empty() {
+            cues.push(Cue { start_ms, end_ms, text });
+        }
+    }
+    Ok(cues)
+}
+
+fn clock(ms: u64) -> String {
+    format!("{:02}:{:02}:{:02}.{:03}", ms / 3_600_000, (ms / 60_000) % 60, (ms / 1000) % 60, ms % 1000)
+}
+
+/// The cues as whisper-style `[start --> end]  text` lines, which the rest of the app already reads as timing.
+pub(crate) fn timestamped_text(cues: &[Cue]) -> String {
+    cues.iter().map(|cue| format!("[{} --> {}]  {}\n", clock(cue.start_ms), clock(cue.end_ms), cue.text)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srt_cues_become_timestamped_lines_without_markup() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:04,500\r\n<i>Hello</i> there,\r\nteam.\r\n\r\n\
+2\r\n00:00:05,000 --> 00:00:06,250\r\n\r\n3\r\n01:02:03,004 --> 01:02:05,000\r\nBye\r\n";
+        let cues = parse_srt(srt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0], Cue { start_ms: 1000, end_ms: 4500, text: "Hello there, team.".to_string() });
+        assert_eq!(
+            timestamped_text(&cues),
+            "[00:00:01.000 --> 00:00:04.500]  Hello there, team.\n[01:02:03.004 --> 01:02:05.000]  Bye\n"
+        );
+
+        let error =
+            parse_srt("1\n00:00:01,000 --> 00:00:02,000\nOk\n\n2\n00:00:03,000 -> 00:00:04,000\nBroken\n").unwrap_err();
+        assert_eq!(error.cue, 2);
+        assert!(error.message.contains("timing line"), "{}", error.message);
+        assert_eq!(parse_srt("1\n00:00:05,000 --> 00:00:04,000\nBackwards\n").unwrap_err().cue, 1);
+        let error = parse_srt("1\n00:00:61,000 --> 00:01:04,000\nNo\n").unwrap_err();
+        assert_eq!(error.message, "invalid start time `00:00:61,000`");
+    }
+
+    #[test]
+    fn vtt_skips_notes_and_keeps_speakers() {
+        let vtt = "WEBVTT - call\n\nNOTE exported by vendor\n\nSTYLE\n::cue { color: white }\n\n\
+intro\n00:01.000 --> 00:03.000 align:start\n<v Alice>Hi &amp; welcome</v>\n\n\
+00:00:04.000 --> 00:00:05.000\n<c.loud>Thanks</c>\n";
+        let cues = parse_vtt(vtt).unwrap();
+        assert_eq!(
+            cues,
+            vec![
+                Cue { start_ms: 1000, end_ms: 3000, text: "Alice: Hi & welcome".to_string() },
+                Cue { start_ms: 4000, end_ms: 5000, text: "Thanks".to_string() },
+            ]
+        );
+
+        assert_eq!(parse_vtt("1\n00:01.000 --> 00:02.000\nHi\n").unwrap_err().cue, 0);
+        let error = parse_vtt("WEBVTT\n\nNOTE skipped\n\n00:01.000 --> 00:02.000\nA\n\n00:03.0 --> 00:04.000\nB\n")
+            .unwrap_err();
+        assert_eq!((error.cue, error.message.as_str()), (2, "invalid start time `00:03.0`"));
+    }
+}
@@ -0,0 +1,52 @@
+use encoding_rs::{Encoding, WINDOWS_1252};
+
+/// Text written by another tool, whatever it was saved as: UTF-8 with or without a BOM, UTF-16 with a BOM, or a
+/// legacy 8-bit code page, read as Windows-1252 (a superset of Latin-1). Nothing is dropped; bytes that fit none of
+/// these become U+FFFD.
+pub(crate) fn decode(bytes: &[u8]) -> String {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        return encoding.decode_without_bom_handling(&bytes[bom_len..]).0.into_owned();
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        // A few broken sequences in otherwise UTF-8 text (a cut multi-byte character) are damage, not a code page.
+        Err(_) if has_utf8_multibyte(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Err(_) => WINDOWS_1252.decode_without_bom_handling(bytes).0.into_owned(),
+    }
+}
+
+fn has_utf8_multibyte(bytes: &[u8]) -> bool {
+    bytes.utf8_chunks().any(|chunk| !chunk.valid().is_ascii())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf16_and_utf8_files_decode_with_or_without_a_bom() {
+        let srt = decode(include_bytes!("../tests/fixtures/transcript-utf16le.srt"));
+        assert!(srt.starts_with("1\r\n00:00:01,000 --> 00:00:03,500\r\nGrüße aus Köln – “Hallo”."), "{srt}");
+        assert!(srt.ends_with("Danke schön.\r\n"));
+
+        let text = "Grüße – ok";
+        let utf16 = |encode: fn(u16) -> [u8; 2]| text.encode_utf16().flat_map(encode).collect::<Vec<u8>>();
+        assert_eq!(decode(&[b"\xFF\xFE".to_vec(), utf16(u16::to_le_bytes)].concat()), text);
+        assert_eq!(decode(&[b"\xFE\xFF".to_vec(), utf16(u16::to_be_bytes)].concat()), text);
+        assert_eq!(decode(format!("\u{feff}{text}").as_bytes()), text);
+        assert_eq!(decode(text.as_bytes()), text);
+    }
+
+    #[test]
+    fn legacy_code_pages_are_read_as_windows_1252() {
+        let latin1 = decode(include_bytes!("../tests/fixtures/transcript-latin1.txt"));
+        assert_eq!(latin1, "Réunion du lundi : café, crème brûlée et 10 £ de budget.\nÇa marche, à demain.\n");
+        assert_eq!(decode(b"\x93Quoted\x94 \x80 5 \x96 \x81"), "“Quoted” € 5 – \u{81}");
+
+        // UTF-8 with one character cut in half keeps everything else instead of turning into mojibake.
+        let mut damaged = "Grüße aus Köln".as_bytes().to_vec();
+        damaged.truncate(damaged.len() - 3);
+        damaged.extend_from_slice(b" end");
+        assert_eq!(decode(&damaged), "Grüße aus K\u{fffd} end");
+    }
+}
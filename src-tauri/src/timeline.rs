@@ -0,0 +1,564 @@
+use serde::{Deserialize, Serialize};
+
+const MAX_CHAPTER_TITLE_CHARS: usize = 100;
+
+// Keeps a misconfigured window length from producing one window per millisecond.
+const MIN_WINDOW_MS: u64 = 1000;
+
+/// A stretch of transcript text placed on the recording timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TimedSegment {
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DynamicsWindow {
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) word_count: usize,
+    pub(crate) words_per_minute: f64,
+    /// Share of the window covered by speech. Without diarization this is the total across speakers.
+    pub(crate) talk_ratio: f64,
+    pub(crate) sentence_count: usize,
+    pub(crate) question_count: usize,
+    pub(crate) question_density: f64,
+    pub(crate) sentiment: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Chapter {
+    pub(crate) start_ms: u64,
+    pub(crate) title: String,
+}
+
+pub(crate) fn parse_clock(value: &str) -> Option<f64> {
+    let mut total = 0.0;
+    for part in value.trim().split(':') {
+        total = total * 60.0 + part.trim().parse::<f64>().ok()?;
+    }
+    Some(total)
+}
+
+/// Splits a whisper line like `[00:00:01.000 --> 00:00:04.000]  Hello` into start and end seconds and the tail.
+pub(crate) fn parse_timestamped_line(line: &str) -> Option<(f64, f64, &str)> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let close = rest.find(']')?;
+    let (start, end) = rest[..close].split_once("-->")?;
+    Some((parse_clock(start)?, parse_clock(end)?, &rest[(close + 1)..]))
+}
+
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        let at_boundary = chars.peek().map(|(_, next)| next.is_whitespace()).unwrap_or(true);
+        if matches!(ch, '.' | '?' | '!') && at_boundary {
+            let end = index + ch.len_utf8();
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+    }
+    let tail = text[start..].trim();
+    if !tail.is_empty() {
+        sentences.push(tail);
+    }
+    sentences
+}
+
+/// Uses whisper timestamps when the transcript kept them; otherwise spreads sentences over
+/// `duration_ms` by word count, which is an estimate rather than a measurement.
+pub(crate) fn timed_segments(text: &str, duration_ms: u64) -> (Vec<TimedSegment>, bool) {
+    let stamped: Vec<TimedSegment> = text
+        .lines()
+        .filter_map(parse_timestamped_line)
+        .filter(|(_, _, tail)| !tail.trim().is_empty())
+        .map(|(start, end, tail)| TimedSegment {
+            start_ms: (start.max(0.0) * 1000.0).round() as u64,
+            end_ms: (end.max(start).max(0.0) * 1000.0).round() as u64,
+            text: tail.trim().to_string(),
+        })
+        .collect();
+    if !stamped.is_empty() {
+        return (stamped, true);
+    }
+
+    let sentences = sentences(text);
+    let total_words: usize = sentences.iter().map(|sentence| sentence.split_whitespace().count()).sum();
+    if total_words == 0 || duration_ms == 0 {
+        return (Vec::new(), false);
+    }
+    let mut words_before = 0;
+    let segments = sentences
+        .into_iter()
+        .map(|sentence| {
+            let words = sentence.split_whitespace().count();
+            let start_ms = duration_ms * words_before as u64 / total_words as u64;
+            words_before += words;
+            TimedSegment {
+                start_ms,
+                end_ms: duration_ms * words_before as u64 / total_words as u64,
+                text: sentence.to_string(),
+            }
+        })
+        .collect();
+    (segments, false)
+}
+
+/// A timestamped transcript line and where it sits in the transcript, in characters.
+/// The line owns every character from `line_start` up to the next timestamped line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LineSpan {
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) line_start: usize,
+    pub(crate) text_start: usize,
+    pub(crate) text_end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TranscriptLocation {
+    pub(crate) segment_index: usize,
+    pub(crate) at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TimeLocation {
+    pub(crate) segment_index: usize,
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) char_start: usize,
+    pub(crate) char_end: usize,
+}
+
+pub(crate) fn timestamped_spans(text: &str) -> Vec<LineSpan> {
+    let mut spans = Vec::new();
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        if let Some((start, end, tail)) = parse_timestamped_line(line) {
+            let spoken = tail.trim();
+            if !spoken.is_empty() {
+                // `tail` is a suffix of `line`, so the spoken text starts this many bytes in.
+                let spoken_byte = line.len() - tail.trim_start().len();
+                let text_start = line_start + line[..spoken_byte].chars().count();
+                spans.push(LineSpan {
+                    start_ms: (start.max(0.0) * 1000.0).round() as u64,
+                    end_ms: (end.max(start).max(0.0) * 1000.0).round() as u64,
+                    line_start,
+                    text_start,
+                    text_end: text_start + spoken.chars().count(),
+                });
+            }
+        }
+        line_start += line.chars().count();
+    }
+    spans
+}
+
+/// The segments whisper.cpp writes with `-oj`; `offsets` are milliseconds from the start of the audio.
+pub(crate) fn whisper_json_segments(json_text: &str) -> Vec<TimedSegment> {
+    let Ok(body) = serde_json::from_str::<serde_json::Value>(json_text) else {
+        return Vec::new();
+    };
+    let Some(items) = body.get("transcription").and_then(|items| items.as_array()) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let offsets = item.get("offsets")?;
+            let start_ms = offsets.get("from")?.as_u64()?;
+            let end_ms = offsets.get("to")?.as_u64()?.max(start_ms);
+            let text = item.get("text")?.as_str()?.trim();
+            (!text.is_empty()).then(|| TimedSegment { start_ms, end_ms, text: text.to_string() })
+        })
+        .collect()
+}
+
+/// Places timed segments in `text`, the transcript as stored, which the cleanup options may have changed since
+/// whisper wrote it. Each segment is looked for after the previous one; one whose words are gone is skipped, and
+/// its characters belong to the segment before.
+pub(crate) fn align_segments(text: &str, segments: &[TimedSegment]) -> Vec<LineSpan> {
+    let mut spans: Vec<LineSpan> = Vec::new();
+    let (mut cursor, mut cursor_chars) = (0, 0);
+    for segment in segments {
+        let needle = segment.text.trim();
+        if needle.is_empty() {
+            continue;
+        }
+        let Some(found) = text[cursor..].find(needle) else {
+            continue;
+        };
+        let text_start = cursor_chars + text[cursor..cursor + found].chars().count();
+        spans.push(LineSpan {
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            line_start: spans.last().map(|span| span.text_end).unwrap_or(0),
+            text_start,
+            text_end: text_start + needle.chars().count(),
+        });
+        cursor += found + needle.len();
+        cursor_chars = text_start + needle.chars().count();
+    }
+    spans
+}
+
+/// Hands each span of `text` to the piece of the recording it starts in, with times rebased onto that piece and
+/// cut off at its end. `split_points_ms` are the cuts in ascending order; there is one piece more than cuts.
+pub(crate) fn split_segments(text: &str, spans: &[LineSpan], split_points_ms: &[u64]) -> Vec<Vec<TimedSegment>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pieces = vec![Vec::new(); split_points_ms.len() + 1];
+    for span in spans {
+        let index = split_points_ms.partition_point(|&cut| cut <= span.start_ms);
+        let piece_start = if index == 0 { 0 } else { split_points_ms[index - 1] };
+        let piece_end = split_points_ms.get(index).copied().unwrap_or(u64::MAX);
+        let spoken: String = chars[span.text_start.min(chars.len())..span.text_end.min(chars.len())].iter().collect();
+        if spoken.trim().is_empty() {
+            continue;
+        }
+        pieces[index].push(TimedSegment {
+            start_ms: span.start_ms - piece_start,
+            end_ms: span.end_ms.min(piece_end).max(span.start_ms) - piece_start,
+            text: spoken.trim().to_string(),
+        });
+    }
+    pieces
+}
+
+/// Interpolates linearly inside the owning line's spoken text; the timestamp prefix maps to the line start.
+pub(crate) fn locate_offset(spans: &[LineSpan], char_offset: usize) -> Option<TranscriptLocation> {
+    let segment_index = spans.iter().rposition(|span| span.line_start <= char_offset).unwrap_or(0);
+    let span = spans.get(segment_index)?;
+    let at_ms = if char_offset <= span.text_start {
+        span.start_ms
+    } else if char_offset >= span.text_end {
+        span.end_ms
+    } else {
+        let progress = (char_offset - span.text_start) as u64;
+        let length = (span.text_end - span.text_start) as u64;
+        span.start_ms + (span.end_ms - span.start_ms) * progress / length
+    };
+    Some(TranscriptLocation { segment_index, at_ms })
+}
+
+/// Picks the last line starting at or before `at_ms`, so gaps between lines resolve to the line before.
+pub(crate) fn locate_time(spans: &[LineSpan], at_ms: u64) -> Option<TimeLocation> {
+    let segment_index = spans.iter().rposition(|span| span.start_ms <= at_ms).unwrap_or(0);
+    let span = spans.get(segment_index)?;
+    Some(TimeLocation {
+        segment_index,
+        start_ms: span.start_ms,
+        end_ms: span.end_ms,
+        char_start: span.text_start,
+        char_end: span.text_end,
+    })
+}
+
+/// Buckets segments by start time into fixed windows covering the whole call.
+pub(crate) fn dynamics_windows(segments: &[TimedSegment], window_ms: u64, duration_ms: u64) -> Vec<DynamicsWindow> {
+    let window_ms = window_ms.max(MIN_WINDOW_MS);
+    let call_end = segments.iter().map(|segment| segment.end_ms).max().unwrap_or(0).max(duration_ms);
+    let window_count = call_end.div_ceil(window_ms).max(1);
+
+    (0..window_count)
+        .map(|index| {
+            let start_ms = index * window_ms;
+            let end_ms = (start_ms + window_ms).min(call_end.max(start_ms + 1));
+            let in_window: Vec<&TimedSegment> = segments
+                .iter()
+                .filter(|segment| segment.start_ms >= start_ms && segment.start_ms < start_ms + window_ms)
+                .collect();
+            let word_count: usize = in_window.iter().map(|segment| segment.text.split_whitespace().count()).sum();
+            let speech_ms: u64 = segments
+                .iter()
+                .map(|segment| segment.end_ms.min(end_ms).saturating_sub(segment.start_ms.max(start_ms)))
+                .sum();
+            let window_sentences: Vec<&str> = in_window.iter().flat_map(|segment| sentences(&segment.text)).collect();
+            let question_count = window_sentences.iter().filter(|sentence| sentence.ends_with('?')).count();
+            let length_ms = (end_ms - start_ms) as f64;
+
+            DynamicsWindow {
+                start_ms,
+                end_ms,
+                word_count,
+                words_per_minute: word_count as f64 * 60_000.0 / length_ms,
+                talk_ratio: (speech_ms as f64 / length_ms).min(1.0),
+                sentence_count: window_sentences.len(),
+                question_count,
+                question_density: if window_sentences.is_empty() {
+                    0.0
+                } else {
+                    question_count as f64 / window_sentences.len() as f64
+                },
+                sentiment: None,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn window_text(segments: &[TimedSegment], window: &DynamicsWindow) -> String {
+    segments
+        .iter()
+        .filter(|segment| segment.start_ms >= window.start_ms && segment.start_ms < window.end_ms.max(window.start_ms + 1))
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats milliseconds as `mm:ss`, or `h:mm:ss` once the call passes an hour.
+pub(crate) fn short_clock(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let (hours, minutes, secs) = (total_secs / 3600, (total_secs / 60) % 60, total_secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes:02}:{secs:02}")
+    }
+}
+
+/// Pulls the outermost `[...]` span out of a model reply that may wrap JSON in prose or code fences.
+pub(crate) fn extract_json_array(reply: &str) -> Option<&str> {
+    let start = reply.find('[')?;
+    let end = reply.rfind(']')?;
+    (end > start).then(|| &reply[start..=end])
+}
+
+pub(crate) fn parse_sentiment_scores(reply: &str, expected: usize) -> Option<Vec<f64>> {
+    let scores: Vec<f64> = serde_json::from_str(extract_json_array(reply)?).ok()?;
+    (scores.len() == expected).then(|| scores.into_iter().map(|score| score.clamp(-1.0, 1.0)).collect())
+}
+
+/// Checks a model reply against the chapter contract: ascending starts inside the call, non-empty titles.
+/// The first chapter is pinned to 0 because YouTube ignores chapter lists that start later.
+pub(crate) fn parse_chapters(reply: &str, call_end_ms: u64) -> Result<Vec<Chapter>, String> {
+    let json = extract_json_array(reply).ok_or_else(|| "the reply contains no JSON array".to_string())?;
+    let mut chapters: Vec<Chapter> =
+        serde_json::from_str(json).map_err(|e| format!("the JSON does not match [{{start_ms, title}}]: {e}"))?;
+    if chapters.is_empty() {
+        return Err("the chapter list is empty".to_string());
+    }
+    for (index, chapter) in chapters.iter_mut().enumerate() {
+        chapter.title = chapter.title.trim().chars().take(MAX_CHAPTER_TITLE_CHARS).collect();
+        if chapter.title.is_empty() {
+            return Err(format!("chapter {} has an empty title", index + 1));
+        }
+        if call_end_ms > 0 && chapter.start_ms >= call_end_ms {
+            return Err(format!("chapter {} starts after the call ends", index + 1));
+        }
+    }
+    if chapters.windows(2).any(|pair| pair[1].start_ms <= pair[0].start_ms) {
+        return Err("chapter start times are not strictly ascending".to_string());
+    }
+    chapters[0].start_ms = 0;
+    Ok(chapters)
+}
+
+pub(crate) fn youtube_chapters(chapters: &[Chapter]) -> String {
+    chapters
+        .iter()
+        .map(|chapter| format!("{} {}\n", short_clock(chapter.start_ms), chapter.title))
+        .collect()
+}
+
+pub(crate) fn markdown_chapters(chapters: &[Chapter]) -> String {
+    let mut markdown = String::from("## Chapters\n\n");
+    for chapter in chapters {
+        markdown.push_str(&format!("- **{}** {}\n", short_clock(chapter.start_ms), chapter.title));
+    }
+    markdown
+}
+
+// `=`, `;`, `#`, `\\` and line breaks are syntax in ffmpeg metadata files.
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Renders global tags and chapters as an ffmpeg metadata file (`;FFMETADATA1`). Each chapter ends where
+/// the next begins and the last runs to `duration_ms`, or one second past its start when that is unknown.
+pub(crate) fn ffmetadata(tags: &[(&str, &str)], chapters: &[Chapter], duration_ms: u64) -> String {
+    let mut file = String::from(";FFMETADATA1\n");
+    for (key, value) in tags {
+        file.push_str(&format!("{key}={}\n", escape_ffmetadata(value)));
+    }
+    for (index, chapter) in chapters.iter().enumerate() {
+        let end_ms = match chapters.get(index + 1) {
+            Some(next) => next.start_ms,
+            None if duration_ms > chapter.start_ms => duration_ms,
+            None => chapter.start_ms + 1000,
+        };
+        file.push_str(&format!(
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={end_ms}\ntitle={}\n",
+            chapter.start_ms,
+            escape_ffmetadata(&chapter.title)
+        ));
+    }
+    file
+}
+
+pub(crate) fn dynamics_markdown_table(windows: &[DynamicsWindow]) -> String {
+    let mut table = String::from("| Window | WPM | Talk | Questions | Sentiment |\n|---|---|---|---|---|\n");
+    for window in windows {
+        table.push_str(&format!(
+            "| {}–{} | {:.0} | {:.0}% | {} | {} |\n",
+            short_clock(window.start_ms),
+            short_clock(window.end_ms),
+            window.words_per_minute,
+            window.talk_ratio * 100.0,
+            window.question_count,
+            window
+                .sentiment
+                .map(|score| format!("{score:+.2}"))
+                .unwrap_or_else(|| "–".to_string())
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timed_segments_prefer_whisper_timestamps_over_estimates() {
+        let text = "[00:00:00.000 --> 00:00:30.000]  Hello there.\n[00:02:05.000 --> 00:02:10.000]  Is the price final?";
+        let (segments, timed) = timed_segments(text, 600_000);
+        assert!(timed);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].start_ms, 125_000);
+
+        let (estimated, timed) = timed_segments("One two three. Four five six seven?", 70_000);
+        assert!(!timed);
+        assert_eq!(estimated[1].start_ms, 30_000);
+        assert_eq!(estimated[1].end_ms, 70_000);
+    }
+
+    #[test]
+    fn whisper_json_segments_are_found_in_the_cleaned_transcript() {
+        let json = r#"{"result": {"language": "de"}, "transcription": [
+            {"offsets": {"from": 0, "to": 2400}, "text": " Grüße aus Köln."},
+            {"offsets": {"from": 2400, "to": 3000}, "text": " [MUSIC]"},
+            {"offsets": {"from": 3000, "to": 5200}, "text": " Wie geht's?"},
+            {"offsets": {"from": 5200, "to": 5200}, "text": " "}
+        ]}"#;
+        let segments = whisper_json_segments(json);
+        assert_eq!(segments.len(), 3);
+        assert_eq!((segments[2].start_ms, segments[2].end_ms), (3000, 5200));
+        assert!(whisper_json_segments("not json").is_empty());
+
+        let spans = align_segments("Grüße aus Köln.\nWie geht's?\n", &segments);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].line_start, spans[0].text_start, spans[0].text_end), (0, 0, 15));
+        assert_eq!((spans[1].line_start, spans[1].text_start, spans[1].text_end), (15, 16, 27));
+        assert_eq!(locate_time(&spans, 4000).unwrap().char_start, 16);
+        assert_eq!(locate_offset(&spans, 20).unwrap().at_ms, 3800);
+    }
+
+    #[test]
+    fn split_segments_rebase_each_piece_onto_its_own_start() {
+        let text = "[00:00:00.000 --> 00:00:04.000]  First caller.\n\
+                    [00:00:04.000 --> 00:00:12.000]  Goodbye then.\n\
+                    [00:00:12.000 --> 00:00:15.000]  Second caller.\n";
+        let pieces = split_segments(text, &timestamped_spans(text), &[10_000]);
+        assert_eq!(pieces.len(), 2);
+        let times = |piece: &[TimedSegment]| {
+            piece.iter().map(|segment| (segment.start_ms, segment.end_ms, segment.text.clone())).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            times(&pieces[0]),
+            vec![(0, 4_000, "First caller.".to_string()), (4_000, 10_000, "Goodbye then.".to_string())]
+        );
+        assert_eq!(times(&pieces[1]), vec![(2_000, 5_000, "Second caller.".to_string())]);
+        assert!(split_segments(text, &timestamped_spans(text), &[1_000, 2_000])[1].is_empty());
+    }
+
+    #[test]
+    fn dynamics_windows_bucket_words_talk_time_and_questions() {
+        let text = "[00:00:00.000 --> 00:01:00.000]  We can start now. Ready?\n[00:02:30.000 --> 00:03:00.000]  Why is it late? It shipped.";
+        let (segments, _) = timed_segments(text, 180_000);
+        let windows = dynamics_windows(&segments, 120_000, 180_000);
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].word_count, 5);
+        assert!((windows[0].talk_ratio - 0.5).abs() < 1e-9);
+        assert_eq!(windows[1].end_ms, 180_000);
+        assert_eq!(windows[1].question_count, 1);
+        assert!((windows[1].question_density - 0.5).abs() < 1e-9);
+        assert!((windows[1].words_per_minute - 6.0).abs() < 1e-9);
+        assert_eq!(window_text(&segments, &windows[1]), "Why is it late? It shipped.");
+    }
+
+    #[test]
+    fn parse_chapters_validates_order_and_pins_the_first_start() {
+        let chapters = parse_chapters(
+            "Here you go:\n[{\"start_ms\": 4000, \"title\": \" Intro \"}, {\"start_ms\": 3725000, \"title\": \"Pricing\"}]",
+            4_000_000,
+        )
+        .unwrap();
+        assert_eq!(chapters[0], Chapter { start_ms: 0, title: "Intro".to_string() });
+        assert_eq!(youtube_chapters(&chapters), "00:00 Intro\n1:02:05 Pricing\n");
+
+        let backwards = r#"[{"start_ms": 5000, "title": "A"}, {"start_ms": 1000, "title": "B"}]"#;
+        assert!(parse_chapters(backwards, 10_000).is_err());
+        assert!(parse_chapters(r#"[{"start_ms": 20000, "title": "Late"}]"#, 10_000).is_err());
+        assert!(parse_chapters(r#"[{"start": 0, "name": "A"}]"#, 10_000).is_err());
+    }
+
+    #[test]
+    fn parse_sentiment_scores_requires_one_score_per_window() {
+        assert_eq!(parse_sentiment_scores("Scores:\n```json\n[0.5, -2]\n```", 2), Some(vec![0.5, -1.0]));
+        assert_eq!(parse_sentiment_scores("[0.5]", 2), None);
+        assert_eq!(parse_sentiment_scores("no idea", 1), None);
+    }
+
+    #[test]
+    fn offsets_and_times_map_through_timestamped_lines() {
+        let text = "[00:00:00.000 --> 00:00:10.000]  Hello there.\n[00:00:20.000 --> 00:00:30.000]  Привет мир!";
+        let spans = timestamped_spans(text);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[1].line_start, spans[1].text_start, spans[1].text_end), (46, 79, 90));
+
+        assert_eq!(locate_offset(&spans, 5).unwrap(), TranscriptLocation { segment_index: 0, at_ms: 0 });
+        assert_eq!(locate_offset(&spans, 39).unwrap().at_ms, 5_000);
+        assert_eq!(locate_offset(&spans, 84).unwrap().at_ms, 24_545);
+        assert_eq!(locate_offset(&spans, 200).unwrap().at_ms, 30_000);
+
+        let location = locate_time(&spans, 15_000).unwrap();
+        assert_eq!((location.segment_index, location.char_start, location.char_end), (0, 33, 45));
+        assert_eq!(locate_time(&spans, 25_000).unwrap().segment_index, 1);
+        assert!(locate_offset(&timestamped_spans("Edited by hand."), 3).is_none());
+    }
+
+    #[test]
+    fn ffmetadata_escapes_values_and_closes_every_chapter() {
+        let chapters = vec![
+            Chapter {
+                start_ms: 0,
+                title: "Intro; small talk".to_string(),
+            },
+            Chapter {
+                start_ms: 90_000,
+                title: "Price = 10 #deal".to_string(),
+            },
+        ];
+        let file = ffmetadata(&[("title", "Acme\\Call"), ("date", "2026-03-01")], &chapters, 300_000);
+        assert_eq!(
+            file,
+            ";FFMETADATA1\ntitle=Acme\\\\Call\ndate=2026-03-01\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=90000\ntitle=Intro\\; small talk\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=90000\nEND=300000\ntitle=Price \\= 10 \\#deal\n"
+        );
+        assert!(ffmetadata(&[], &chapters, 0).ends_with("START=90000\nEND=91000\ntitle=Price \\= 10 \\#deal\n"));
+        assert_eq!(ffmetadata(&[("title", "Line\nbreak")], &[], 0), ";FFMETADATA1\ntitle=Line\\\nbreak\n");
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+// Long enough for any real version line; the fallback keeps whatever a tool printed first, so it is capped.
+const RAW_LINE_CHARS: usize = 200;
+
+/// Arguments tried in turn until one prints a recognisable version. whisper.cpp builds without `--version`
+/// reject the flag, and `--help` is the next best thing.
+fn probe_args(tool: &str) -> &'static [&'static [&'static str]] {
+    match tool {
+        "ffmpeg" => &[&["-version"]],
+        _ => &[&["--version"], &["--help"]],
+    }
+}
+
+/// The versions of the external tools seen during this app run. Each tool is asked once, on first use, since
+/// the binaries on PATH are not expected to change underneath a running app.
+#[derive(Default)]
+pub(crate) struct ToolVersions {
+    probed: Mutex<BTreeMap<String, Option<String>>>,
+}
+
+impl ToolVersions {
+    /// The version of `tool`, or None when it could not be run at all.
+    pub(crate) fn get(&self, tool: &str) -> Option<String> {
+        if let Some(known) = self.probed.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(tool) {
+            return known.clone();
+        }
+        // Probed without the lock so a slow tool does not hold up readers of the others.
+        let version = probe(tool);
+        let mut probed = self.probed.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        probed.entry(tool.to_string()).or_insert(version).clone()
+    }
+
+    /// The versions of `tools` that could be found, keyed by tool name.
+    pub(crate) fn for_tools(&self, tools: &[&str]) -> BTreeMap<String, String> {
+        tools.iter().filter_map(|tool| Some((tool.to_string(), self.get(tool)?))).collect()
+    }
+}
+
+fn probe(tool: &str) -> Option<String> {
+    let mut fallback = None;
+    for args in probe_args(tool) {
+        let Ok(output) = Command::new(tool).args(*args).stdin(Stdio::null()).output() else {
+            return None;
+        };
+        let text = format!("{}\n{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        match describe(&text) {
+            Some(Described::Parsed(version)) => return Some(version),
+            // The first line of a run that went through says more than a complaint about an unknown flag.
+            Some(Described::Raw(line)) if output.status.success() || fallback.is_none() => fallback = Some(line),
+            _ => {}
+        }
+    }
+    fallback
+}
+
+#[derive(Debug, PartialEq)]
+enum Described {
+    Parsed(String),
+    Raw(String),
+}
+
+/// Reads a version out of what a tool printed: the token after the first `version` that contains a digit, as in
+/// `ffmpeg version 6.1.1 Copyright ...` or `whisper.cpp version: 1.7.4`. Output in any other shape is kept as
+/// its first non-blank line.
+fn describe(output: &str) -> Option<Described> {
+    if let Some(version) = output.lines().find_map(version_after_keyword) {
+        return Some(Described::Parsed(version));
+    }
+    let line = output.lines().map(str::trim).find(|line| !line.is_empty())?;
+    Some(Described::Raw(line.chars().take(RAW_LINE_CHARS).collect()))
+}
+
+fn version_after_keyword(line: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets, so `at` indexes `line` as well.
+    let at = line.to_ascii_lowercase().find("version")?;
+    let rest = line[at + "version".len()..].trim_start_matches([' ', '\t', ':', '=']);
+    let token = rest.split_whitespace().next()?.trim_end_matches([',', ';', ')']);
+    token.bytes().any(|byte| byte.is_ascii_digit()).then(|| token.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_version(output: &str) -> Option<String> {
+        describe(output).map(|described| match described {
+            Described::Parsed(version) | Described::Raw(version) => version,
+        })
+    }
+
+    #[test]
+    fn versions_are_read_from_known_formats_and_raw_lines_kept_otherwise() {
+        let ffmpeg =
+            "ffmpeg version n6.1.1-3ubuntu5 Copyright (c) 2000-2023 the FFmpeg developers\nbuilt with gcc 13\n";
+        assert_eq!(parse_version(ffmpeg).as_deref(), Some("n6.1.1-3ubuntu5"));
+        assert_eq!(parse_version("\nwhisper.cpp Version: 1.7.4,\n").as_deref(), Some("1.7.4"));
+        assert_eq!(parse_version("whisper-cli\nversion=1.5.0 (abc123)\n").as_deref(), Some("1.5.0"));
+
+        // No usable version: the first line that says anything is kept instead.
+        let help = "\nusage: whisper-cli [options] file0.wav file1.wav ...\n\noptions:\n  --version  unknown\n";
+        assert_eq!(describe(help), Some(Described::Raw("usage: whisper-cli [options] file0.wav file1.wav ...".into())));
+        assert_eq!(parse_version(&format!("{}\n", "x".repeat(500))).map(|line| line.len()), Some(RAW_LINE_CHARS));
+        assert_eq!(parse_version(" \n\n"), None);
+    }
+
+    #[test]
+    fn missing_tools_are_remembered_as_missing() {
+        let versions = ToolVersions::default();
+        assert_eq!(versions.get("beyond-call-no-such-tool"), None);
+        assert!(versions.probed.lock().unwrap().contains_key("beyond-call-no-such-tool"));
+        assert!(versions.for_tools(&["beyond-call-no-such-tool"]).is_empty());
+    }
+}
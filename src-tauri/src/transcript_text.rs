@@ -0,0 +1,177 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Which whisper artifacts to remove from a transcript before it is stored. All off by default, because
+/// timestamps drive the timeline features and some teams want the noise tags kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TextCleanupOptions {
+    pub(crate) strip_noise_tags: bool,
+    pub(crate) strip_timestamps: bool,
+    pub(crate) collapse_blank_lines: bool,
+}
+
+fn timestamp_prefix() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\s*\[\d{1,2}(?::\d{2}){1,2}(?:[.,]\d+)?\s*-->\s*\d{1,2}(?::\d{2}){1,2}(?:[.,]\d+)?\]\s*")
+            .expect("valid timestamp regex")
+    })
+}
+
+// `[MUSIC]`, `[BLANK_AUDIO]`, `(laughs)`, `(upbeat music)`: letters only and a few words at most, so
+// spoken parentheticals with numbers or longer asides survive.
+fn noise_tag() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\[\s*\p{L}[\p{L}_ -]{0,38}\]|\(\s*\p{L}+(?:[ -]\p{L}+){0,3}\s*\)|\*\s*\p{L}+(?: \p{L}+){0,3}\s*\*|♪+")
+            .expect("valid noise tag regex")
+    })
+}
+
+fn strip_noise(line: &str) -> String {
+    let stripped = noise_tag().replace_all(line, " ");
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(crate) fn clean(text: &str, options: TextCleanupOptions) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        if !options.strip_noise_tags && !options.strip_timestamps {
+            lines.push(line.to_string());
+            continue;
+        }
+        let (prefix, spoken) = match timestamp_prefix().find(line) {
+            Some(found) => (found.as_str(), &line[found.end()..]),
+            None => ("", line),
+        };
+        let spoken = if options.strip_noise_tags {
+            let cleaned = strip_noise(spoken);
+            // A line that held nothing but tags goes away entirely, timestamp included.
+            if cleaned.is_empty() && !spoken.trim().is_empty() {
+                continue;
+            }
+            cleaned
+        } else {
+            spoken.to_string()
+        };
+        if options.strip_timestamps || prefix.is_empty() {
+            lines.push(spoken);
+        } else {
+            lines.push(format!("{}  {}", prefix.trim_end(), spoken.trim_start()));
+        }
+    }
+
+    if options.collapse_blank_lines {
+        lines.dedup_by(|line, previous| line.trim().is_empty() && previous.trim().is_empty());
+        while lines.first().is_some_and(|line| line.trim().is_empty()) {
+            lines.remove(0);
+        }
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+    }
+
+    let mut cleaned = lines.join("\n");
+    if text.ends_with('\n') && !cleaned.is_empty() {
+        cleaned.push('\n');
+    }
+    cleaned
+}
+
+fn word_counts(text: &str) -> HashMap<String, i64> {
+    let mut counts = HashMap::new();
+    for line in text.lines() {
+        let spoken = timestamp_prefix().find(line).map_or(line, |found| &line[found.end()..]);
+        for word in spoken.split(|ch: char| !ch.is_alphanumeric()).filter(|word| !word.is_empty()) {
+            *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// How much of a transcript an edit touched, from 0 to 100: the words removed plus the words added, as a
+/// share of the words in both versions. Only word counts are compared, so case, punctuation, timestamps
+/// and word order are all ignored: a fixed typo in a long call scores near zero, reordered paragraphs
+/// score zero, and replacing half the text scores about 50.
+pub(crate) fn change_percent(before: &str, after: &str) -> f64 {
+    let (before, after) = (word_counts(before), word_counts(after));
+    let total: i64 = before.values().chain(after.values()).sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let changed: i64 = before
+        .keys()
+        .chain(after.keys().filter(|word| !before.contains_key(*word)))
+        .map(|word| (before.get(word).unwrap_or(&0) - after.get(word).unwrap_or(&0)).abs())
+        .sum();
+    changed as f64 * 100.0 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHISPER_OUTPUT: &str = "[00:00:00.000 --> 00:00:04.000]   [MUSIC]\n\
+[00:00:04.000 --> 00:00:09.500]   Thanks for joining (laughs) so early.\n\
+\n\
+\n\
+[00:00:09.500 --> 00:00:12.000]   We shipped 3 releases (in 2023) already.\n\
+[00:00:12.000 --> 00:00:14.000]   [BLANK_AUDIO]\n";
+
+    fn options(strip_noise_tags: bool, strip_timestamps: bool, collapse_blank_lines: bool) -> TextCleanupOptions {
+        TextCleanupOptions {
+            strip_noise_tags,
+            strip_timestamps,
+            collapse_blank_lines,
+        }
+    }
+
+    #[test]
+    fn default_options_leave_the_transcript_alone() {
+        assert_eq!(clean(WHISPER_OUTPUT, TextCleanupOptions::default()), WHISPER_OUTPUT);
+    }
+
+    #[test]
+    fn noise_tags_go_but_timestamps_and_spoken_parentheticals_stay() {
+        assert_eq!(
+            clean(WHISPER_OUTPUT, options(true, false, false)),
+            "[00:00:04.000 --> 00:00:09.500]  Thanks for joining so early.\n\
+\n\
+\n\
+[00:00:09.500 --> 00:00:12.000]  We shipped 3 releases (in 2023) already.\n"
+        );
+        assert_eq!(clean("♪ la la ♪ *upbeat music* Hello", options(true, false, false)), "la la Hello");
+    }
+
+    #[test]
+    fn all_options_produce_plain_prose() {
+        assert_eq!(
+            clean(WHISPER_OUTPUT, options(true, true, true)),
+            "Thanks for joining so early.\n\nWe shipped 3 releases (in 2023) already.\n"
+        );
+        assert_eq!(
+            clean("\n\n[00:01.000 --> 00:02.000] Hi.\n\n\n\nBye.\n\n", options(false, true, true)),
+            "Hi.\n\nBye.\n"
+        );
+    }
+
+    #[test]
+    fn change_percent_separates_typo_fixes_from_rewrites() {
+        let original = "[00:00:00.000 --> 00:00:04.000]  We agreed on a pilot for the sales team.\n\
+[00:00:04.000 --> 00:00:08.000]  Pricing comes next week.\n";
+        assert_eq!(change_percent(original, original), 0.0);
+        assert_eq!(change_percent(original, "We agreed on a pilot for the sales team. Pricing comes next week."), 0.0);
+        assert_eq!(change_percent("", ""), 0.0);
+        assert_eq!(change_percent("", "Hello"), 100.0);
+
+        let typo = change_percent(original, &original.replace("Pricing", "Pricng"));
+        assert!(typo > 0.0 && typo < 10.0, "{typo}");
+        let rewrite = change_percent(
+            original,
+            "We agreed on a pilot for the sales team. Legal wants another review first.",
+        );
+        assert!((30.0..60.0).contains(&rewrite), "{rewrite}");
+    }
+}
@@ -8,6 +8,7 @@ import type {
   Entry,
   EntryBundle,
   Folder,
+  JobOutcome,
   PromptRole,
   RecordingDevice,
   RecordingSource
@@ -132,6 +133,11 @@ const RU_TRANSLATIONS: Record<string, string> = {
   "Transcribing latest recording": "Транскрибация последней записи",
   "Signal level": "Уровень сигнала",
   Captured: "Записано",
+  Elapsed: "Прошло",
+  "Space left": "Места осталось",
+  "Input is clipping": "Вход перегружен",
+  "Generated from a shortened transcript": "Создано по сокращённой расшифровке",
+  "characters from the middle were left out": "символов из середины пропущено",
   "Transcription Language": "Язык транскрибации",
   "Auto detect": "Автоопределение",
   Russian: "Русский",
@@ -184,6 +190,24 @@ const RU_TRANSLATIONS: Record<string, string> = {
   "Save Summary Prompt": "Сохранить промпт для Саммари",
   "Summary Prompt updated": "Промпт для Саммари обновлен",
   "Recording signal meter": "Индикатор сигнала записи",
+  "Recording paused because the computer went to sleep. Resume when ready.":
+    "Запись приостановлена, так как компьютер уснул. Продолжите, когда будете готовы.",
+  "The recorder stopped while the computer was asleep. Stop the recording to save what was captured.":
+    "Запись прервалась, пока компьютер спал. Остановите запись, чтобы сохранить записанное.",
+  "Scheduled recording started": "Запланированная запись началась",
+  "The library database was damaged and could not be read, so the app started with an empty library.":
+    "База данных библиотеки была повреждена и не читается, поэтому приложение запущено с пустой библиотекой.",
+  "The library database was damaged and has been rebuilt from what could still be read.":
+    "База данных библиотеки была повреждена и восстановлена из того, что удалось прочитать.",
+  "Partly lost": "Частично утеряно",
+  "The damaged file was kept at": "Повреждённый файл сохранён в",
+  "The scheduled recording was missed": "Запланированная запись пропущена",
+  "The scheduled recording could not start": "Не удалось начать запланированную запись",
+  "Queued until the recording stops": "В очереди до окончания записи",
+  "Recording stopped. Transcription is queued behind another recording.":
+    "Запись остановлена. Транскрибация ждет окончания другой записи.",
+  "A queued job finished": "Задача из очереди выполнена",
+  "A queued job failed": "Задача из очереди не выполнена",
   "Save Transcript": "Сохранить транскрипт",
   completed: "завершено",
   saved: "сохранено",
@@ -375,6 +399,10 @@ function latestByType(revisions: ArtifactRevision[], type: ArtifactType) {
     .sort((a, b) => b.version - a.version)[0];
 }
 
+function isQueued(outcome: unknown): outcome is JobOutcome<unknown> {
+  return typeof outcome === "object" && outcome !== null && (outcome as JobOutcome<unknown>).status === "queued";
+}
+
 function buildTree(folders: Folder[]) {
   const byParent = new Map<string | null, Folder[]>();
   for (const folder of folders) {
@@ -462,6 +490,9 @@ export default function App() {
   const [recordingPaused, setRecordingPaused] = useState(false);
   const [recordingLevel, setRecordingLevel] = useState(0);
   const [recordingBytes, setRecordingBytes] = useState(0);
+  const [recordingElapsed, setRecordingElapsed] = useState(0);
+  const [recordingRemaining, setRecordingRemaining] = useState<number | null>(null);
+  const [recordingClipping, setRecordingClipping] = useState(false);
   const [meterBars, setMeterBars] = useState<number[]>(() => Array.from({ length: 24 }, () => 0.02));
   const [transcriptionLanguage, setTranscriptionLanguage] = useState<string>("auto");
   const [detailTab, setDetailTab] = useState<"transcript" | "summary" | "analysis" | "critique">("transcript");
@@ -610,13 +641,13 @@ export default function App() {
     setArtifactDrafts(nextDrafts);
   }
 
-  async function runTask(task: () => Promise<void>, successNotice?: string) {
+  async function runTask(task: () => Promise<unknown>, successNotice?: string) {
     setBusy(true);
     setError(null);
     setNotice(null);
     const currentEntryId = selectedEntryId;
     try {
-      await task();
+      const outcome = await task();
       await reloadBootstrap(true);
       if (currentEntryId) {
         try {
@@ -626,7 +657,9 @@ export default function App() {
           setEntryBundle(null);
         }
       }
-      if (successNotice) {
+      if (isQueued(outcome)) {
+        setNotice(tt("Queued until the recording stops"));
+      } else if (successNotice) {
         setNotice(successNotice);
       }
     } catch (taskError) {
@@ -648,8 +681,18 @@ export default function App() {
       setBusy(true);
       setError(null);
       try {
+        // Date filters and day buckets follow the UI's time zone rather than the machine's.
+        void api.setClientTimezone(-new Date().getTimezoneOffset()).catch(() => undefined);
         await reloadBootstrap(false);
         await loadRecordingDevices(true);
+        // After a window reload the session id is gone from memory, but the recording may still be running.
+        const [active] = await api.listRecordingSessions();
+        if (active) {
+          setRecordingSessionId(active.session_id);
+          setRecordingPaused(active.paused);
+          setSelectedEntryId(active.entry_id);
+          await loadEntryBundle(active.entry_id);
+        }
         void api
           .prepareAiBackend()
           .then((message) => {
@@ -679,6 +722,9 @@ export default function App() {
     if (!recordingSessionId) {
       setRecordingLevel(0);
       setRecordingBytes(0);
+      setRecordingElapsed(0);
+      setRecordingRemaining(null);
+      setRecordingClipping(false);
       setMeterBars(Array.from({ length: 24 }, () => 0.02));
       return;
     }
@@ -694,6 +740,10 @@ export default function App() {
         const normalizedLevel = Math.max(0, Math.min(1, meter.level));
         setRecordingLevel(normalizedLevel);
         setRecordingBytes(meter.bytes_written);
+        setRecordingElapsed(meter.elapsed_seconds);
+        setRecordingRemaining(meter.estimated_remaining_seconds);
+        setRecordingClipping(meter.clipping);
+        setRecordingPaused(meter.paused);
         setMeterBars((previous) => {
           const next = [...previous.slice(1)];
           const bar = normalizedLevel < 0.02
@@ -718,6 +768,77 @@ export default function App() {
     };
   }, [recordingSessionId]);
 
+  useEffect(() => {
+    const unlisten = api.onRecordingInterrupted((interrupted) => {
+      if (interrupted.recorder_exited) {
+        setNotice(tt("The recorder stopped while the computer was asleep. Stop the recording to save what was captured."));
+      } else if (interrupted.paused) {
+        setRecordingPaused(true);
+        setNotice(tt("Recording paused because the computer went to sleep. Resume when ready."));
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [uiLanguage]);
+
+  useEffect(() => {
+    const unlistenStarted = api.onScheduledRecordingStarted((started) => {
+      setRecordingSessionId(started.session_id);
+      setRecordingPaused(false);
+      setSelectedEntryId(started.entry_id);
+      void loadEntryBundle(started.entry_id);
+      setNotice(tt("Scheduled recording started"));
+    });
+    const unlistenFailed = api.onScheduledRecordingFailed((failed) => {
+      const headline = failed.expired
+        ? tt("The scheduled recording was missed")
+        : tt("The scheduled recording could not start");
+      setError(`${headline}: ${failed.error}`);
+    });
+    return () => {
+      void unlistenStarted.then((stop) => stop());
+      void unlistenFailed.then((stop) => stop());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [uiLanguage]);
+
+  useEffect(() => {
+    const unlisten = api.onDeferredJobFinished((finished) => {
+      void reloadBootstrap(true);
+      if (finished.entry_id === selectedEntryId) {
+        void loadEntryBundle(finished.entry_id);
+      }
+      if (finished.error) {
+        setError(`${tt("A queued job failed")}: ${finished.error}`);
+      } else {
+        setNotice(tt("A queued job finished"));
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [uiLanguage, selectedEntryId]);
+
+  useEffect(() => {
+    void api.getOnboardingStatus().then((status) => {
+      const recovery = status.database_recovery;
+      if (!recovery) {
+        return;
+      }
+      const headline = status.database_reset
+        ? tt("The library database was damaged and could not be read, so the app started with an empty library.")
+        : tt("The library database was damaged and has been rebuilt from what could still be read.");
+      const lost = recovery.damaged_tables.length
+        ? ` ${tt("Partly lost")}: ${recovery.damaged_tables.join(", ")}.`
+        : "";
+      setError(`${headline}${lost} ${tt("The damaged file was kept at")} ${recovery.quarantined_path}`);
+    });
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
   useEffect(() => {
     setDetailTab("transcript");
   }, [selectedEntryId]);
@@ -806,6 +927,16 @@ export default function App() {
     return `${(value / (1024 * 1024)).toFixed(1)} MB`;
   }
 
+  function formatSeconds(value: number) {
+    const hours = Math.floor(value / 3600);
+    const minutes = Math.floor((value % 3600) / 60);
+    const seconds = Math.floor(value % 60);
+    if (hours > 0) {
+      return `${hours}h ${minutes}m`;
+    }
+    return `${minutes}:${String(seconds).padStart(2, "0")}`;
+  }
+
   function sourceKey(source: RecordingSource) {
     return `${source.format}::${source.input}`;
   }
@@ -818,7 +949,8 @@ export default function App() {
     return {
       label: device.name,
       format: device.format,
-      input: device.input
+      input: device.input,
+      device_name: device.name
     };
   }
 
@@ -899,7 +1031,8 @@ export default function App() {
       setError(tt("Select a folder first"));
       return;
     }
-    const title = workspaceNameDraft.trim() || defaultLabel(tt("Entry"));
+    // A blank title is named by the backend from the default title template.
+    const title = workspaceNameDraft.trim();
     runTask(async () => {
       await api.createEntry(selectedFolderId, title);
       setWorkspaceNameDraft("");
@@ -950,6 +1083,9 @@ export default function App() {
 
   const activeArtifactType: ArtifactType =
     detailTab === "summary" ? "summary" : detailTab === "analysis" ? "analysis" : critiqueType;
+  const activeArtifactTruncation = entryBundle
+    ? latestByType(entryBundle.artifact_revisions, activeArtifactType)?.truncated_chars ?? null
+    : null;
   const sourceControlsDisabled = busy || Boolean(recordingSessionId) || transcribingAfterStop;
 
   return (
@@ -1300,10 +1436,14 @@ export default function App() {
 
                         setTranscribingAfterStop(true);
                         try {
-                          await api.transcribeEntry(activeEntry.id, transcriptionLanguage);
+                          const outcome = await api.transcribeEntry(activeEntry.id, transcriptionLanguage);
                           await reloadBootstrap(true);
                           await loadEntryBundle(activeEntry.id);
-                          setNotice(tt("Recording stopped and transcribed"));
+                          setNotice(
+                            isQueued(outcome)
+                              ? tt("Recording stopped. Transcription is queued behind another recording.")
+                              : tt("Recording stopped and transcribed")
+                          );
                         } catch (taskError) {
                           const message = taskError instanceof Error ? taskError.message : String(taskError);
                           setNotice(null);
@@ -1329,7 +1469,10 @@ export default function App() {
                 </p>
                 {recordingSessionId && (
                   <>
-                    <div className="meter-strip" aria-label={tt("Recording signal meter")}>
+                    <div
+                      className={recordingClipping ? "meter-strip clipping" : "meter-strip"}
+                      aria-label={tt("Recording signal meter")}
+                    >
                       {meterBars.map((bar, index) => (
                         <span
                           key={`bar-${index}`}
@@ -1340,7 +1483,13 @@ export default function App() {
                     </div>
                     <p className="help-text">
                       {tt("Signal level")}: {Math.round(recordingLevel * 100)}% | {tt("Captured")}:{" "}
-                      {formatBytes(recordingBytes)}
+                      {formatBytes(recordingBytes)} | {tt("Elapsed")}: {formatSeconds(recordingElapsed)}
+                      {recordingRemaining !== null && (
+                        <>
+                          {" "}| {tt("Space left")}: ~{formatSeconds(recordingRemaining)}
+                        </>
+                      )}
+                      {recordingClipping && <span className="clipping-text"> | {tt("Input is clipping")}</span>}
                     </p>
                   </>
                 )}
@@ -1469,6 +1618,12 @@ export default function App() {
                     placeholder={`${tt("Draft")} ${artifactLabel(activeArtifactType).toLowerCase()}...`}
                   />
                 )}
+                {detailTab !== "transcript" && activeArtifactTruncation ? (
+                  <p className="truncation-note">
+                    {tt("Generated from a shortened transcript")}: {activeArtifactTruncation}{" "}
+                    {tt("characters from the middle were left out")}
+                  </p>
+                ) : null}
               </div>
             </div>
           </section>
@@ -1,58 +1,482 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
+  ActiveRecording,
+  ActivityDay,
+  ActivityLogEntry,
+  AppSettings,
+  AppliedTemplateFolder,
+  ArtifactGenerated,
   ArtifactType,
+  AudioSafetyCopy,
+  AudioSafetyUsage,
+  BatchItemResult,
   BootstrapState,
+  CallDigest,
+  CallDynamics,
+  ClipboardFormat,
+  Comparison,
+  ControlServerStatus,
+  DeferredJobFinished,
+  Diagnostics,
+  Draft,
+  DraftTarget,
   EntryBundle,
+  EntryChapters,
+  EntryFilter,
+  ExportCompleted,
+  ExportFile,
+  ExportProgress,
+  ExportUsage,
+  EntryOperation,
+  EntryPage,
+  EntrySearchResult,
+  FolderMergeResult,
+  FolderTemplate,
+  FolderTemplateNode,
+  FolderTreeNode,
+  GenerationStats,
+  JobOutcome,
+  JobSchedulerState,
+  ModelInfo,
+  OnboardingStatus,
+  PendingPurge,
+  PromptExperiment,
+  PromptExperimentProgress,
+  PromptImportReport,
   PromptRole,
+  PromptTestResult,
+  QuickSearchResult,
   RecordingDevice,
+  RecordingFinalized,
+  RecordingInterrupted,
+  RedactionCategory,
+  RedactionMatch,
   RecordingMeter,
-  RecordingSource
+  RecordingSource,
+  RecordingSegment,
+  PlaybackStatus,
+  RestoreOutcome,
+  ScheduledRecording,
+  ScheduledRecordingFailed,
+  ScheduledRecordingStarted,
+  TimeLocation,
+  TranscriptLocation,
+  SemanticSearchResult,
+  Settings,
+  SettingsImportReport,
+  SettingsPatch,
+  SourcePreset,
+  TextCleanupOptions,
+  TimelineEvent,
+  TranscriptImportFormat,
+  TrashStats,
+  WhisperModelRepair
 } from "./types";
 
+// Every failed command is forwarded to the backend log so bug reports include it.
+async function call<T>(command: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await invoke<T>(command, args);
+  } catch (error) {
+    void invoke("record_command_error", { command, message: String(error) }).catch(() => undefined);
+    throw error;
+  }
+}
+
+// Exports run as background jobs; this subscribes before starting one so a fast job cannot finish unobserved.
+async function runExport(start: () => Promise<string>, onStarted?: (jobId: string) => void): Promise<string> {
+  let jobId: string | null = null;
+  const early = new Map<string, ExportCompleted>();
+  let settle: (completed: ExportCompleted) => void = () => undefined;
+  const finished = new Promise<ExportCompleted>((resolve) => {
+    settle = resolve;
+  });
+  const unlisten = await listen<ExportCompleted>("export://completed", ({ payload }) => {
+    if (jobId === null) {
+      early.set(payload.job_id, payload);
+    } else if (payload.job_id === jobId) {
+      settle(payload);
+    }
+  });
+  try {
+    jobId = await start();
+    onStarted?.(jobId);
+    const completed = early.get(jobId) ?? (await finished);
+    if (completed.cancelled) {
+      throw new Error("Export cancelled");
+    }
+    if (completed.path === null) {
+      throw new Error(completed.error ?? "Export failed");
+    }
+    return completed.path;
+  } finally {
+    unlisten();
+  }
+}
+
 export const api = {
-  bootstrapState: () => invoke<BootstrapState>("bootstrap_state"),
-  getEntryBundle: (entryId: string) =>
-    invoke<EntryBundle>("get_entry_bundle", { entryId }),
+  /** @deprecated Use getAppSettings, getFolderTree, and listEntries. */
+  bootstrapState: (includeArchived = false) =>
+    call<BootstrapState>("bootstrap_state", { includeArchived }),
+  getAppSettings: () => call<AppSettings>("get_app_settings"),
+  getSettings: () => call<Settings>("get_settings"),
+  updateSettings: (patch: SettingsPatch) => call<Settings>("update_settings", { patch }),
+  getDataChangeCounter: () => call<number>("get_data_change_counter"),
+  getFolderTree: (includeArchived = false) =>
+    call<FolderTreeNode[]>("get_folder_tree", { includeArchived }),
+  listEntries: (filter: EntryFilter | null = null, offset = 0, limit: number | null = null) =>
+    call<EntryPage>("list_entries", { filter, offset, limit }),
+  getEntryBundle: (entryId: string, fullHistory = false) =>
+    call<EntryBundle>("get_entry_bundle", { entryId, fullHistory }),
+  /** Omit `artifactType` for a transcript revision. */
+  getRevisionText: (entryId: string, version: number, artifactType: ArtifactType | null = null) =>
+    call<string>("get_revision_text", { entryId, artifactType, version }),
   createFolder: (name: string, parentId: string | null) =>
-    invoke<void>("create_folder", { name, parentId }),
+    call<void>("create_folder", { name, parentId }),
+  createFolderTemplate: (name: string, tree: FolderTemplateNode[]) =>
+    call<FolderTemplate>("create_folder_template", { name, tree }),
+  listFolderTemplates: () => call<FolderTemplate[]>("list_folder_templates"),
+  deleteFolderTemplate: (name: string) => call<void>("delete_folder_template", { name }),
+  /** Creates the whole tree under `parentId` (top level when null), or nothing if any part fails. */
+  applyFolderTemplate: (templateName: string, parentId: string | null) =>
+    call<AppliedTemplateFolder[]>("apply_folder_template", { templateName, parentId }),
   renameFolder: (folderId: string, name: string) =>
-    invoke<void>("rename_folder", { folderId, name }),
+    call<void>("rename_folder", { folderId, name }),
+  setFolderAppearance: (folderId: string, color: string | null, icon: string | null) =>
+    call<void>("set_folder_appearance", { folderId, color, icon }),
+  reorderFolders: (parentId: string | null, orderedFolderIds: string[]) =>
+    call<void>("reorder_folders", { parentId, orderedFolderIds }),
+  mergeFolders: (sourceFolderId: string, targetFolderId: string) =>
+    call<FolderMergeResult>("merge_folders", { sourceFolderId, targetFolderId }),
   createEntry: (folderId: string, title: string) =>
-    invoke<void>("create_entry", { folderId, title }),
+    call<void>("create_entry", { folderId, title }),
+  updateDefaultTitleTemplate: (template: string) =>
+    call<void>("update_default_title_template", { template }),
   renameEntry: (entryId: string, title: string) =>
-    invoke<void>("rename_entry", { entryId, title }),
+    call<void>("rename_entry", { entryId, title }),
+  setEntryLocked: (entryId: string, locked: boolean) =>
+    call<void>("set_entry_locked", { entryId, locked }),
+  setEntryArchived: (entryId: string, archived: boolean) =>
+    call<void>("set_entry_archived", { entryId, archived }),
+  splitEntry: (entryId: string, splitPointsMs: number[], titles: string[]) =>
+    call<string[]>("split_entry", { entryId, splitPointsMs, titles }),
+  mergeEntries: (firstEntryId: string, secondEntryId: string) =>
+    call<void>("merge_entries", { firstEntryId, secondEntryId }),
+  searchEntries: (filter: EntryFilter) =>
+    call<EntrySearchResult>("search_entries", { filter }),
+  exportEntriesCsv: (filter: EntryFilter, destinationPath: string) =>
+    call<number>("export_entries_csv", { filter, destinationPath }),
+  createSavedSearch: (name: string, filter: EntryFilter) =>
+    call<string>("create_saved_search", { name, filter }),
+  updateSavedSearch: (searchId: string, name: string, filter: EntryFilter) =>
+    call<void>("update_saved_search", { searchId, name, filter }),
+  deleteSavedSearch: (searchId: string) => call<void>("delete_saved_search", { searchId }),
+  runSavedSearch: (searchId: string) =>
+    call<EntrySearchResult>("run_saved_search", { searchId }),
   moveToTrash: (entityType: "folder" | "entry", id: string) =>
-    invoke<void>("move_to_trash", { entityType, id }),
-  restoreFromTrash: (entityType: "folder" | "entry", id: string) =>
-    invoke<void>("restore_from_trash", { entityType, id }),
+    call<void>("move_to_trash", { entityType, id }),
+  restoreFromTrash: (entityType: "folder" | "entry", id: string, intoRecovered = false) =>
+    call<RestoreOutcome>("restore_from_trash", { entityType, id, intoRecovered }),
+  moveEntries: (entryIds: string[], targetFolderId: string) =>
+    call<BatchItemResult[]>("move_entries", { entryIds, targetFolderId }),
+  trashEntries: (entryIds: string[]) => call<BatchItemResult[]>("trash_entries", { entryIds }),
+  restoreEntries: (entryIds: string[], intoRecovered = false) =>
+    call<BatchItemResult[]>("restore_entries", { entryIds, intoRecovered }),
   purgeEntity: (entityType: "folder" | "entry", id: string) =>
-    invoke<void>("purge_entity", { entityType, id }),
-  startRecording: (entryId: string, sources: RecordingSource[]) =>
-    invoke<string>("start_recording", { entryId, sources }),
+    call<string>("purge_entity", { entityType, id }),
+  undoPurge: (operationId: string) => call<void>("undo_purge", { operationId }),
+  listPendingPurges: () => call<PendingPurge[]>("list_pending_purges"),
+  getTrashStats: () => call<TrashStats>("get_trash_stats"),
+  finalizePurges: () => call<number>("finalize_purges"),
+  updateAutoNormalize: (enabled: boolean) => call<void>("update_auto_normalize", { enabled }),
+  updateTextCleanupOptions: (options: TextCleanupOptions) =>
+    call<void>("update_text_cleanup_options", { options }),
+  updateExportSettings: (filenameTemplate: string, defaultDirectory: string) =>
+    call<void>("update_export_settings", { filenameTemplate, defaultDirectory }),
+  getControlServerStatus: () => call<ControlServerStatus>("get_control_server_status"),
+  setControlServerEnabled: (enabled: boolean) =>
+    call<ControlServerStatus>("set_control_server_enabled", { enabled }),
+  cleanTranscriptText: (text: string, options: TextCleanupOptions) =>
+    call<string>("clean_transcript_text", { text, options }),
+  compareEntries: (entryIds: string[], dimensionPromptRole: string) =>
+    call<Comparison>("compare_entries", { entryIds, dimensionPromptRole }),
+  listComparisons: (folderId: string | null) => call<Comparison[]>("list_comparisons", { folderId }),
+  deleteComparison: (comparisonId: string) => call<void>("delete_comparison", { comparisonId }),
+  exportComparisonMarkdown: (comparisonId: string) =>
+    call<string>("export_comparison_markdown", { comparisonId }),
+  /** Dates are local `YYYY-MM-DD` days, both included. */
+  generateDigest: (fromDate: string, toDate: string, folderId: string | null = null) =>
+    call<CallDigest>("generate_digest", { fromDate, toDate, folderId }),
+  listDigests: () => call<CallDigest[]>("list_digests"),
+  exportDigest: (digestId: string) => call<string>("export_digest", { digestId }),
+  updatePurgeGraceHours: (hours: number) => call<void>("update_purge_grace_hours", { hours }),
+  /** `autoBalance` evens out the loudness of multiple sources before they are mixed. */
+  startRecording: (entryId: string, sources: RecordingSource[], autoBalance = false) =>
+    call<string>("start_recording", { entryId, sources, autoBalance }),
+  startRecordingFromPreset: (entryId: string, presetName: string, autoBalance = false) =>
+    call<string>("start_recording", { entryId, presetName, autoBalance }),
   setRecordingPaused: (sessionId: string, paused: boolean) =>
-    invoke<void>("set_recording_paused", { sessionId, paused }),
+    call<void>("set_recording_paused", { sessionId, paused }),
   stopRecording: (sessionId: string) =>
-    invoke<void>("stop_recording", { sessionId }),
+    call<void>("stop_recording", { sessionId }),
   getRecordingMeter: (sessionId: string) =>
-    invoke<RecordingMeter>("recording_meter", { sessionId }),
+    call<RecordingMeter>("recording_meter", { sessionId }),
+  /** Sessions still recording, longest-running first; a reloaded window uses this to reattach. */
+  listRecordingSessions: () => call<ActiveRecording[]>("list_recording_sessions"),
+  findSessionForEntry: (entryId: string) =>
+    call<ActiveRecording | null>("find_session_for_entry", { entryId }),
+  /** `startAt` is an RFC 3339 time in the future; the entry and sources are checked right away. */
+  scheduleRecording: (
+    entryId: string,
+    sources: RecordingSource[],
+    startAt: string,
+    autoBalance: boolean | null = null
+  ) =>
+    call<ScheduledRecording>("schedule_recording", { entryId, sources, startAt, autoBalance }),
+  listScheduledRecordings: () => call<ScheduledRecording[]>("list_scheduled_recordings"),
+  cancelScheduledRecording: (scheduleId: string) => call<void>("cancel_scheduled_recording", { scheduleId }),
+  /** Transcription, cleanup and generation resolve to `queued` while a recording holds them back. */
   transcribeEntry: (entryId: string, language: string | null = null) =>
-    invoke<void>("transcribe_entry", { entryId, language }),
-  generateArtifact: (entryId: string, artifactType: ArtifactType) =>
-    invoke<void>("generate_artifact", { entryId, artifactType }),
-  updateTranscript: (entryId: string, text: string, language: string) =>
-    invoke<void>("update_transcript", { entryId, text, language }),
-  updateArtifact: (entryId: string, artifactType: ArtifactType, text: string) =>
-    invoke<void>("update_artifact", { entryId, artifactType, text }),
+    call<JobOutcome<null>>("transcribe_entry", { entryId, language }),
+  retranscribeEntry: (
+    entryId: string,
+    modelName: string | null = null,
+    language: string | null = null
+  ) => call<JobOutcome<null>>("retranscribe_entry", { entryId, modelName, language }),
+  cleanupTranscript: (entryId: string) =>
+    call<JobOutcome<null>>("cleanup_transcript", { entryId }),
+  previewRedactions: (entryId: string) =>
+    call<RedactionMatch[]>("preview_redactions", { entryId }),
+  redactTranscript: (entryId: string, categories: RedactionCategory[]) =>
+    call<number>("redact_transcript", { entryId, categories }),
+  restoreRedactedTranscript: (entryId: string, version: number) =>
+    call<string>("restore_redacted_transcript", { entryId, version }),
+  /** The result is a warning when Ollama does not list `modelOverride`, otherwise null. */
+  generateArtifact: (
+    entryId: string,
+    artifactType: ArtifactType,
+    useRedacted = false,
+    modelOverride: string | null = null,
+    transcriptVersion: number | null = null
+  ) =>
+    call<JobOutcome<string | null>>("generate_artifact", {
+      entryId,
+      artifactType,
+      useRedacted,
+      modelOverride,
+      transcriptVersion
+    }),
+  updateTranscript: (
+    entryId: string,
+    text: string,
+    language: string,
+    expectedVersion: number | null = null
+  ) => call<void>("update_transcript", { entryId, text, language, expectedVersion }),
+  /** `textOrPath` is read as a file when it names one; resolves to the new transcript version. */
+  importTranscript: (
+    entryId: string,
+    textOrPath: string,
+    format: TranscriptImportFormat,
+    language: string | null = null
+  ) => call<number>("import_transcript", { entryId, textOrPath, language, format }),
+  updateArtifact: (
+    entryId: string,
+    artifactType: ArtifactType,
+    text: string,
+    expectedVersion: number | null = null
+  ) => call<void>("update_artifact", { entryId, artifactType, text, expectedVersion }),
+  analyzeCallDynamics: (entryId: string) =>
+    call<CallDynamics>("analyze_call_dynamics", { entryId }),
+  getCallDynamics: (entryId: string) =>
+    call<CallDynamics | null>("get_call_dynamics", { entryId }),
+  generateChapters: (entryId: string) =>
+    call<EntryChapters>("generate_chapters", { entryId }),
+  getChapters: (entryId: string) => call<EntryChapters | null>("get_chapters", { entryId }),
+  exportChapters: (entryId: string, format: "youtube" | "markdown") =>
+    call<string>("export_chapters", { entryId, format }),
+  exportEntryAudio: (entryId: string, format: "m4a" | "mp3", embedMetadata: boolean) =>
+    call<string>("export_entry_audio", { entryId, format, embedMetadata }),
+  setEntryMetadata: (entryId: string, key: string, value: string) =>
+    call<void>("set_entry_metadata", { entryId, key, value }),
+  deleteEntryMetadata: (entryId: string, key: string) =>
+    call<void>("delete_entry_metadata", { entryId, key }),
+  saveDraft: (
+    entryId: string,
+    targetType: DraftTarget,
+    artifactType: ArtifactType | null,
+    text: string
+  ) => call<void>("save_draft", { entryId, targetType, artifactType, text }),
+  getDraft: (entryId: string, targetType: DraftTarget, artifactType: ArtifactType | null) =>
+    call<Draft | null>("get_draft", { entryId, targetType, artifactType }),
+  discardDraft: (entryId: string, targetType: DraftTarget, artifactType: ArtifactType | null) =>
+    call<void>("discard_draft", { entryId, targetType, artifactType }),
+  getActivityLog: (entityId: string | null = null, limit = 100, before: string | null = null) =>
+    call<ActivityLogEntry[]>("get_activity_log", { entityId, limit, before }),
+  getEntryTimeline: (entryId: string, limit = 100, before: string | null = null) =>
+    call<TimelineEvent[]>("get_entry_timeline", { entryId, limit, before }),
+  getRecentLogs: (lines = 500) => call<string[]>("get_recent_logs", { lines }),
+  openLogDirectory: () => call<void>("open_log_directory"),
+  enableEncryption: (passphrase: string) => call<void>("enable_encryption", { passphrase }),
+  unlock: (passphrase: string) => call<void>("unlock", { passphrase }),
+  disableEncryption: (passphrase: string) => call<void>("disable_encryption", { passphrase }),
+  prepareRecordingPlayback: (entryId: string) =>
+    call<string>("prepare_recording_playback", { entryId }),
+  getDataDir: () => call<string>("get_data_dir"),
+  migrateDataDir: (newPath: string, keepOld = false) =>
+    call<string>("migrate_data_dir", { newPath, keepOld }),
+  exportEntryTransfer: (entryId: string) => call<string>("export_entry_transfer", { entryId }),
+  importEntryTransfer: (folderId: string, archivePath: string, allowDuplicate = false) =>
+    call<string>("import_entry_transfer", { folderId, archivePath, allowDuplicate }),
+  importExportZip: (folderId: string, zipPath: string) => call<string>("import_export_zip", { folderId, zipPath }),
+  getEntryOperations: () => call<Record<string, EntryOperation>>("get_entry_operations"),
+  getGenerationStats: (from: string | null = null, to: string | null = null) =>
+    call<GenerationStats[]>("get_generation_stats", { from, to }),
+  /** Without an offset, the one last sent with setClientTimezone applies. */
+  getActivityCalendar: (fromDate: string, toDate: string, utcOffsetMinutes?: number) =>
+    call<ActivityDay[]>("get_activity_calendar", { fromDate, toDate, utcOffsetMinutes }),
+  /** Call with `-new Date().getTimezoneOffset()`; JavaScript counts the offset the other way round. */
+  setClientTimezone: (utcOffsetMinutes: number) =>
+    call<void>("set_client_timezone", { utcOffsetMinutes }),
+  exportPrompts: () => call<string>("export_prompts"),
+  importPrompts: (json: string, mode: "merge" | "replace") =>
+    call<PromptImportReport>("import_prompts", { json, mode }),
+  exportSettings: (includeSecrets = false) => call<string>("export_settings", { includeSecrets }),
+  importSettings: (json: string, mode: "merge" | "replace") =>
+    call<SettingsImportReport>("import_settings", { json, mode }),
+  testPrompt: (roleOrText: string, sampleTranscript: string, model: string | null = null) =>
+    call<PromptTestResult>("test_prompt", { roleOrText, sampleTranscript, model }),
+  quickSearch: (query: string, limit: number | null = null) =>
+    call<QuickSearchResult[]>("quick_search", { query, limit }),
+  semanticSearch: (query: string, limit: number | null = null) =>
+    call<SemanticSearchResult[]>("semantic_search", { query, limit }),
+  rebuildEmbeddings: () => call<number>("rebuild_embeddings"),
+  updateEmbeddingModel: (modelName: string) =>
+    call<string | null>("update_embedding_model", { modelName }),
   updatePrompt: (role: PromptRole, promptText: string) =>
-    invoke<void>("update_prompt_template", { role, promptText }),
+    call<void>("update_prompt_template", { role, promptText }),
+  /** Artifacts of a role with a schema are generated as JSON and checked against it; null clears it. */
+  setPromptOutputSchema: (role: PromptRole, outputSchema: string | null) =>
+    call<void>("set_prompt_output_schema", { role, outputSchema }),
+  /** `language` may be a code or a language name; it is stored as a code. */
+  savePromptVariant: (role: PromptRole, language: string, promptText: string) =>
+    call<void>("save_prompt_variant", { role, language, promptText }),
+  deletePromptVariant: (role: PromptRole, language: string) =>
+    call<void>("delete_prompt_variant", { role, language }),
+  /** Starts the comparison in the background and returns the experiment id. */
+  comparePrompts: (role: PromptRole, candidatePromptText: string, entryIds: string[]) =>
+    call<string>("compare_prompts", { role, candidatePromptText, entryIds }),
+  getPromptExperiment: (experimentId: string) =>
+    call<PromptExperiment>("get_prompt_experiment", { experimentId }),
+  listPromptExperiments: (role: PromptRole | null = null) =>
+    call<PromptExperiment[]>("list_prompt_experiments", { role }),
+  promotePromptExperiment: (experimentId: string) =>
+    call<void>("promote_prompt_experiment", { experimentId }),
   updateModelName: (modelName: string) =>
-    invoke<void>("update_model_name", { modelName }),
-  prepareAiBackend: () => invoke<string>("prepare_ai_backend"),
-  listWhisperModels: () => invoke<string[]>("list_whisper_models"),
+    call<void>("update_model_name", { modelName }),
+  updateArtifactModel: (artifactType: ArtifactType, modelName: string | null) =>
+    call<string | null>("update_artifact_model", { artifactType, modelName }),
+  updateLlmRetryOptions: (maxRetries: number, initialBackoffMs: number, deadlineSecs: number) =>
+    call<void>("update_llm_retry_options", { maxRetries, initialBackoffMs, deadlineSecs }),
+  updateMaxPromptChars: (maxPromptChars: number) => call<void>("update_max_prompt_chars", { maxPromptChars }),
+  updateArtifactOutputLanguage: (artifactOutputLanguage: string) =>
+    call<void>("update_artifact_output_language", { artifactOutputLanguage }),
+  prepareAiBackend: () => call<string>("prepare_ai_backend"),
+  listWhisperModels: () => call<string[]>("list_whisper_models"),
+  repairWhisperModel: (modelName: string) => call<void>("repair_whisper_model", { modelName }),
   updateWhisperModel: (modelName: string) =>
-    invoke<void>("update_whisper_model", { modelName }),
-  listRecordingDevices: () => invoke<RecordingDevice[]>("list_recording_devices"),
-  listAudioDeviceHints: () => invoke<string[]>("list_audio_device_hints"),
-  exportEntry: (entryId: string) =>
-    invoke<string>("export_entry_markdown", { entryId })
+    call<void>("update_whisper_model", { modelName }),
+  updateTranscriptionChunking: (enabled: boolean, parallelism: number) =>
+    call<void>("update_transcription_chunking", { enabled, parallelism }),
+  updateWhisperRuntimeOptions: (useGpu: boolean, threads: number, beamSize: number) =>
+    call<void>("update_whisper_runtime_options", { useGpu, threads, beamSize }),
+  updateDefaultTranscriptionLanguage: (language: string) =>
+    call<void>("update_default_transcription_language", { language }),
+  setEntryLanguage: (entryId: string, language: string | null) =>
+    call<void>("set_entry_language", { entryId, language }),
+  runDiagnostics: () => call<Diagnostics>("run_diagnostics"),
+  refreshModelInfo: () => call<ModelInfo[]>("refresh_model_info"),
+  listRecordingDevices: () => call<RecordingDevice[]>("list_recording_devices"),
+  listAudioDeviceHints: () => call<string[]>("list_audio_device_hints"),
+  saveSourcePreset: (name: string, sources: RecordingSource[]) =>
+    call<void>("save_source_preset", { name, sources }),
+  listSourcePresets: () => call<SourcePreset[]>("list_source_presets"),
+  deleteSourcePreset: (name: string) => call<void>("delete_source_preset", { name }),
+  resolvePreset: (name: string) => call<RecordingSource[]>("resolve_preset", { name }),
+  listRecordingSegments: (entryId: string) =>
+    call<RecordingSegment[]>("list_recording_segments", { entryId }),
+  rebuildRecording: (entryId: string) => call<void>("rebuild_recording", { entryId }),
+  recomputeDurations: () => call<number>("recompute_durations"),
+  deleteRecordingSegment: (segmentId: string) =>
+    call<void>("delete_recording_segment", { segmentId }),
+  trimRecording: (entryId: string, startMs: number, endMs: number, keepOriginal = true) =>
+    call<void>("trim_recording", { entryId, startMs, endMs, keepOriginal }),
+  commitTrim: (entryId: string) => call<void>("commit_trim", { entryId }),
+  revertTrim: (entryId: string) => call<void>("revert_trim", { entryId }),
+  normalizeRecording: (entryId: string) => call<void>("normalize_recording", { entryId }),
+  listAudioSafetyCopies: (entryId: string) => call<AudioSafetyCopy[]>("list_audio_safety_copies", { entryId }),
+  restoreAudioSafetyCopy: (entryId: string, copyId: string) =>
+    call<void>("restore_audio_safety_copy", { entryId, copyId }),
+  getAudioSafetyUsage: () => call<AudioSafetyUsage>("get_audio_safety_usage"),
+  updateAudioSafetyCopies: (copies: number) => call<number>("update_audio_safety_copies", { copies }),
+  listEntryExports: (entryId: string) => call<ExportFile[]>("list_entry_exports", { entryId }),
+  deleteEntryExport: (entryId: string, filename: string) =>
+    call<void>("delete_entry_export", { entryId, filename }),
+  getExportUsage: () => call<ExportUsage[]>("get_export_usage"),
+  updateExportRetentionCount: (count: number) => call<number>("update_export_retention_count", { count }),
+  playEntryAudio: (entryId: string, startMs?: number) =>
+    call<PlaybackStatus>("play_entry_audio", { entryId, startMs }),
+  pausePlayback: () => call<PlaybackStatus | null>("pause_playback"),
+  seekPlayback: (ms: number) => call<PlaybackStatus>("seek_playback", { ms }),
+  stopPlayback: () => call<void>("stop_playback"),
+  getPlaybackPosition: () => call<PlaybackStatus | null>("get_playback_position"),
+  locateTranscriptOffset: (entryId: string, charOffset: number) =>
+    call<TranscriptLocation>("locate_transcript_offset", { entryId, charOffset }),
+  locateTime: (entryId: string, atMs: number) => call<TimeLocation>("locate_time", { entryId, atMs }),
+  /** Resolves with the archive path; `onStarted` receives the job id for cancelExport. */
+  exportEntry: (
+    entryId: string,
+    useRedacted = false,
+    includeDynamics = false,
+    onStarted?: (jobId: string) => void,
+    includeHistory = false
+  ) =>
+    runExport(
+      () => call<string>("export_entry_markdown", { entryId, useRedacted, includeDynamics, includeHistory }),
+      onStarted
+    ),
+  cancelExport: (jobId: string) => call<void>("cancel_export", { jobId }),
+  /** Each copy command resolves with the number of characters copied. */
+  copyArtifactToClipboard: (entryId: string, artifactType: ArtifactType, format: ClipboardFormat) =>
+    call<number>("copy_artifact_to_clipboard", { entryId, artifactType, format }),
+  copyTranscriptToClipboard: (entryId: string, useRedacted = false) =>
+    call<number>("copy_transcript_to_clipboard", { entryId, useRedacted }),
+  copyEntryMarkdownToClipboard: (
+    entryId: string,
+    format: ClipboardFormat,
+    useRedacted = false,
+    includeDynamics = false,
+  ) => call<number>("copy_entry_markdown_to_clipboard", { entryId, format, useRedacted, includeDynamics }),
+  pauseJobs: () => call<void>("pause_jobs"),
+  resumeJobs: () => call<void>("resume_jobs"),
+  getJobs: () => call<JobSchedulerState>("get_jobs"),
+  getOnboardingStatus: () => call<OnboardingStatus>("get_onboarding_status"),
+  onExportProgress: (handler: (progress: ExportProgress) => void): Promise<UnlistenFn> =>
+    listen<ExportProgress>("export://progress", ({ payload }) => handler(payload)),
+  onRecordingFinalized: (handler: (finalized: RecordingFinalized) => void): Promise<UnlistenFn> =>
+    listen<RecordingFinalized>("recording://finalized", ({ payload }) => handler(payload)),
+  onPromptExperimentProgress: (handler: (progress: PromptExperimentProgress) => void): Promise<UnlistenFn> =>
+    listen<PromptExperimentProgress>("prompt-experiment://progress", ({ payload }) => handler(payload)),
+  onRecordingInterrupted: (handler: (interrupted: RecordingInterrupted) => void): Promise<UnlistenFn> =>
+    listen<RecordingInterrupted>("recording://interrupted", ({ payload }) => handler(payload)),
+  onScheduledRecordingStarted: (handler: (started: ScheduledRecordingStarted) => void): Promise<UnlistenFn> =>
+    listen<ScheduledRecordingStarted>("recording://scheduled_started", ({ payload }) => handler(payload)),
+  onScheduledRecordingFailed: (handler: (failed: ScheduledRecordingFailed) => void): Promise<UnlistenFn> =>
+    listen<ScheduledRecordingFailed>("recording://scheduled_failed", ({ payload }) => handler(payload)),
+  onArtifactGenerated: (handler: (generated: ArtifactGenerated) => void): Promise<UnlistenFn> =>
+    listen<ArtifactGenerated>("artifact://generated", ({ payload }) => handler(payload)),
+  onWhisperModelRepair: (handler: (repair: WhisperModelRepair) => void): Promise<UnlistenFn> =>
+    listen<WhisperModelRepair>("whisper-model://repair", ({ payload }) => handler(payload)),
+  onDeferredJobFinished: (handler: (finished: DeferredJobFinished) => void): Promise<UnlistenFn> =>
+    listen<DeferredJobFinished>("jobs://deferred_finished", ({ payload }) => handler(payload))
 };
@@ -19,18 +19,104 @@ export interface Folder {
   created_at: string;
   updated_at: string;
   deleted_at: string | null;
+  color: string | null;
+  icon: string | null;
+  sort_order: number | null;
+}
+
+export interface FolderTemplateNode {
+  name: string;
+  children?: FolderTemplateNode[];
+  /** Titles of entries created empty in this folder. */
+  entries?: string[];
+}
+
+export interface FolderTemplate {
+  name: string;
+  tree: FolderTemplateNode[];
+  created_at: string;
+  updated_at: string;
+}
+
+/** A folder made by `applyFolderTemplate`; `path` is the template node's names from the top level down. */
+export interface AppliedTemplateFolder {
+  path: string[];
+  folder_id: string;
+  entry_ids: string[];
+}
+
+export interface FolderMergeResult {
+  entries_moved: number;
+  folders_moved: number;
 }
 
 export interface Entry {
   id: string;
   folder_id: string;
   title: string;
-  status: string;
+  status: EntryStatus;
   duration_sec: number;
+  /** Relative to the data directory. */
   recording_path: string | null;
+  recording_outside_data_dir: boolean;
   created_at: string;
   updated_at: string;
   deleted_at: string | null;
+  preferred_language: string | null;
+  has_draft: boolean;
+  is_locked: boolean;
+  archived_at: string | null;
+  transcript_stale: boolean;
+  has_pending_trim: boolean;
+  loudness_normalized: boolean;
+  metadata: Record<string, string>;
+}
+
+export interface ActivityLogEntry {
+  id: string;
+  ts: string;
+  action: string;
+  entity_type: string;
+  entity_id: string | null;
+  details: Record<string, unknown> | null;
+}
+
+export type TimelineEventKind =
+  | "created"
+  | "recorded"
+  | "recording_failed"
+  | "audio_changed"
+  | "trimmed"
+  | "trim_reverted"
+  | "transcribed"
+  | "transcript_imported"
+  | "transcript_cleaned"
+  | "transcript_redacted"
+  | "transcript_merged"
+  | "transcript_edited"
+  | "artifact_generated"
+  | "artifact_edited"
+  | "exported"
+  | "renamed"
+  | "moved"
+  | "trashed"
+  | "restored";
+
+export interface TimelineEvent {
+  id: string;
+  kind: TimelineEventKind;
+  ts: string;
+  details: Record<string, unknown>;
+}
+
+export type DraftTarget = "transcript" | "artifact";
+
+export interface Draft {
+  entry_id: string;
+  target_type: DraftTarget;
+  artifact_type: ArtifactType | null;
+  text: string;
+  updated_at: string;
 }
 
 export interface TranscriptRevision {
@@ -41,8 +127,19 @@ export interface TranscriptRevision {
   language: string;
   is_manual_edit: boolean;
   created_at: string;
+  model: string | null;
+  language_confidence: number | null;
+  derivation: "whisper" | "manual" | "cleanup" | "redaction" | "merge" | "split" | "imported" | null;
+  /** Manual edits only: how much of the previous text changed, 0-100. */
+  change_percent?: number;
+  /** Cleanup revisions only: the LLM that rewrote the text; `model` stays the transcription model. */
+  cleanup_model?: string;
+  /** Older revisions in an entry bundle arrive with empty `text`; fetch it with `getRevisionText`. */
+  text_omitted?: boolean;
 }
 
+export type ContentFormat = "markdown" | "json";
+
 export interface ArtifactRevision {
   id: string;
   entry_id: string;
@@ -53,31 +150,549 @@ export interface ArtifactRevision {
   is_stale: boolean;
   is_manual_edit: boolean;
   created_at: string;
+  model?: string | null;
+  generation_ms?: number | null;
+  prompt_tokens?: number | null;
+  completion_tokens?: number | null;
+  truncated_chars?: number | null;
+  output_language?: string | null;
+  /** Absent for markdown; "json" text matched the prompt's output schema and is pretty-printed. */
+  content_format?: ContentFormat;
+  /** Built on request from an older transcript, so newer transcripts do not make it stale. */
+  is_pinned?: boolean;
+  /** Older revisions in an entry bundle arrive with empty `text`; fetch it with `getRevisionText`. */
+  text_omitted?: boolean;
+}
+
+export interface PromptImportReport {
+  created: string[];
+  updated: string[];
+  skipped: string[];
+}
+
+export interface SectionImportReport {
+  created: string[];
+  updated: string[];
+  removed: string[];
+  skipped: string[];
+}
+
+export interface SettingsImportReport {
+  settings: SectionImportReport;
+  prompts: PromptImportReport;
+  source_presets: SectionImportReport;
+  saved_searches: SectionImportReport;
+  warnings: string[];
+}
+
+export type EntryStatus = "new" | "recording" | "recorded" | "transcribed" | "edited" | "processed";
+
+export interface EntryFilter {
+  folder_id?: string | null;
+  include_subfolders?: boolean;
+  statuses?: EntryStatus[];
+  older_than_days?: number | null;
+  newer_than_days?: number | null;
+  /** Local YYYY-MM-DD days, both included. */
+  created_from?: string | null;
+  created_to?: string | null;
+  transcribed?: boolean | null;
+  title_contains?: string | null;
+  /** Every word must appear in the latest transcript, ignoring case. */
+  text_contains?: string | null;
+  include_archived?: boolean;
+  trashed?: boolean;
+}
+
+/** Where a search hit one entry; ranges are [start, end) offsets in characters (code points), not UTF-16 units. */
+export interface EntrySearchMatch {
+  entry_id: string;
+  title_ranges: [number, number][];
+  /** The transcript revision `ranges` point into; null when the text was not searched. */
+  revision_id: string | null;
+  ranges: [number, number][];
+  /** Every hit in the transcript, including those past the highlight cap. */
+  total_matches: number;
+}
+
+export interface EntrySearchResult {
+  entries: Entry[];
+  /** One per entry, in the same order, when the title or transcript text was searched. */
+  matches: EntrySearchMatch[];
+  warnings: string[];
+}
+
+export interface SavedSearch {
+  id: string;
+  name: string;
+  filter: EntryFilter | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface DynamicsWindow {
+  start_ms: number;
+  end_ms: number;
+  word_count: number;
+  words_per_minute: number;
+  talk_ratio: number;
+  sentence_count: number;
+  question_count: number;
+  question_density: number;
+  sentiment: number | null;
+}
+
+export interface CallDynamics {
+  entry_id: string;
+  transcript_version: number;
+  window_ms: number;
+  timestamped: boolean;
+  model: string;
+  created_at: string;
+  windows: DynamicsWindow[];
+  is_stale: boolean;
+}
+
+export interface ComparisonEntry {
+  entry_id: string;
+  title: string | null;
+  position: number;
+  transcript_version: number;
+  source: "summary" | "transcript";
+}
+
+export interface Comparison {
+  id: string;
+  folder_id: string | null;
+  prompt_role: string;
+  text: string;
+  model: string;
+  created_at: string;
+  entries: ComparisonEntry[];
+  is_stale: boolean;
+}
+
+export interface DigestEntry {
+  entry_id: string;
+  title: string | null;
+  folder_name: string | null;
+  position: number;
+  transcript_version: number;
+  source: "summary" | "transcript";
+}
+
+export interface CallDigest {
+  id: string;
+  folder_id: string | null;
+  from_date: string;
+  to_date: string;
+  text: string;
+  model: string;
+  created_at: string;
+  entries: DigestEntry[];
+}
+
+export interface Chapter {
+  start_ms: number;
+  title: string;
+}
+
+export interface EntryChapters {
+  entry_id: string;
+  transcript_version: number;
+  model: string;
+  created_at: string;
+  is_stale: boolean;
+  chapters: Chapter[];
+}
+
+export interface InvalidChaptersError {
+  code: "INVALID_CHAPTERS";
+  message: string;
+  raw_response: string;
+}
+
+/** Payload of the `data://changed` event; `counter` lets a window detect missed events. */
+export interface DataChange {
+  entity_type: string;
+  entity_id: string | null;
+  action: string;
+  counter: number;
+}
+
+/** Trashed ancestors are restored along with the item unless it was moved into the "Recovered" root folder. */
+export interface RestoreOutcome {
+  revived_folder_ids: string[];
+  recovered_folder_id: string | null;
+  recovered_folder_created: boolean;
+}
+
+export interface BatchItemResult {
+  entry_id: string;
+  ok: boolean;
+  code?: string;
+  message?: string;
+}
+
+export interface AudioSafetyCopy {
+  id: string;
+  operation: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+export interface ExportFile {
+  filename: string;
+  size_bytes: number;
+  created_at: string;
+}
+
+export interface ExportUsage {
+  entry_id: string;
+  files: number;
+  bytes: number;
+}
+
+export interface AudioSafetyUsage {
+  copies: number;
+  bytes: number;
+}
+
+export interface PendingPurge {
+  operation_id: string;
+  entity_type: "folder" | "entry";
+  entity_id: string;
+  staged_at: string;
+  expires_at: string;
+  entry_count: number;
+  staged_bytes: number;
+}
+
+export interface TrashItemStats {
+  entity_type: "folder" | "entry";
+  id: string;
+  name: string;
+  deleted_at: string;
+  entry_count: number;
+  bytes: number;
+  transcript_revisions: number;
+  artifact_revisions: number;
+}
+
+export interface TrashStats {
+  items: TrashItemStats[];
+  entry_count: number;
+  bytes: number;
+  transcript_revisions: number;
+  artifact_revisions: number;
+}
+
+export interface QuickSearchResult {
+  kind: "folder" | "entry";
+  id: string;
+  label: string;
+  folder_id: string | null;
+  score: number;
+  /** Character offsets into `label` to highlight. */
+  positions: number[];
+}
+
+export interface SemanticSearchResult {
+  entry_id: string;
+  title: string;
+  archived: boolean;
+  chunk_index: number;
+  score: number;
+  snippet: string;
+}
+
+export interface EmbeddingProgress {
+  completed_entries: number;
+  total_entries: number;
+}
+
+export interface PromptTestResult {
+  model: string;
+  /** Set when Ollama does not list the requested model. */
+  model_warning: string | null;
+  rendered_prompt: string;
+  /** Sample characters left out to fit the prompt limit. */
+  truncated_chars: number | null;
+  output_language: string | null;
+  output: string;
+  estimated_prompt_tokens: number;
+  prompt_tokens: number | null;
+  completion_tokens: number | null;
+  generation_ms: number;
+}
+
+/** One side of a prompt experiment for one entry. */
+export interface PromptRun {
+  text: string;
+  generation_ms: number;
+  prompt_tokens: number | null;
+  completion_tokens: number | null;
+  truncated_chars: number | null;
+}
+
+/** Both runs are null while the entry waits its turn, or when `error` is set. */
+export interface PromptExperimentResult {
+  entry_id: string;
+  title: string | null;
+  position: number;
+  transcript_version: number | null;
+  /** The stored prompt used for this entry; the language variant when its transcript has one. */
+  baseline_prompt_text: string | null;
+  baseline: PromptRun | null;
+  candidate: PromptRun | null;
+  error: string | null;
+}
+
+export interface PromptExperiment {
+  id: string;
+  role: PromptRole;
+  candidate_prompt_text: string;
+  model: string;
+  created_at: string;
+  /** Null while the experiment is still running. */
+  finished_at: string | null;
+  promoted_at: string | null;
+  results: PromptExperimentResult[];
+}
+
+/** Payload of `prompt-experiment://progress`, sent after each entry; `done` marks the last one. */
+export interface PromptExperimentProgress {
+  experiment_id: string;
+  entry_id: string;
+  completed: number;
+  total: number;
+  error: string | null;
+  done: boolean;
+}
+
+export interface ExportProgress {
+  job_id: string;
+  entry_id: string;
+  bytes_processed: number;
+  bytes_total: number;
+}
+
+export interface ExportCompleted {
+  job_id: string;
+  entry_id: string;
+  path: string | null;
+  error: string | null;
+  cancelled: boolean;
+}
+
+export type TranscriptImportFormat = "txt" | "srt" | "vtt";
+
+export interface WhisperModelRepair {
+  model: string;
+  bytes_downloaded: number;
+  bytes_total: number | null;
+  done: boolean;
+  error: string | null;
+}
+
+export interface ControlServerStatus {
+  enabled: boolean;
+  socket_path: string;
+  token: string | null;
+}
+
+export interface ActivityDay {
+  date: string;
+  entries: number;
+  total_seconds: number;
+  processed: number;
+}
+
+export interface GenerationStats {
+  artifact_type: ArtifactType;
+  model: string | null;
+  generations: number;
+  measured_generations: number;
+  avg_generation_ms: number | null;
+  avg_prompt_tokens: number | null;
+  avg_completion_tokens: number | null;
 }
 
 export interface PromptTemplate {
   role: PromptRole;
+  /** Set on language variants only. */
+  language?: string;
   prompt_text: string;
+  /** JSON Schema for the role's artifacts; only on the generic prompt. */
+  output_schema?: string;
   updated_at: string;
 }
 
-export interface BootstrapState {
-  folders: Folder[];
-  entries: Entry[];
+export interface AppSettings {
   prompt_templates: PromptTemplate[];
+  prompt_variants: Partial<Record<PromptRole, PromptTemplate[]>>;
+  model_name: string;
+  whisper_model: string;
+  transcription_chunking: boolean;
+  transcription_chunk_parallelism: number;
+  whisper_runtime_options: WhisperRuntimeOptions;
+  default_transcription_language: string;
+  llm_retry_options: LlmRetryOptions;
+  max_prompt_chars: number;
+  /** "match_transcript", "english", or a language code. */
+  artifact_output_language: string;
+  auto_regenerate_artifacts: boolean;
+  /** Manual edits changing more than this percent of the words regenerate stale artifacts. */
+  auto_regenerate_threshold_percent: number;
+  max_ollama_jobs: number;
+  embedding_model: string;
+  purge_grace_hours: number;
+  auto_normalize: boolean;
+  audio_safety_copies: number;
+  export_retention_count: number;
+  text_cleanup: TextCleanupOptions;
+  control_server_enabled: boolean;
+  export_filename_template: string;
+  default_title_template: string;
+  export_default_directory: string;
+  saved_searches: SavedSearch[];
+  artifact_models: Partial<Record<ArtifactType, string>>;
+  encryption_enabled: boolean;
+  encryption_unlocked: boolean;
+}
+
+/** The editable settings alone; `updateSettings` takes any subset of these fields. */
+export interface Settings {
   model_name: string;
   whisper_model: string;
+  embedding_model: string;
+  artifact_models: Partial<Record<ArtifactType, string>>;
+  transcription_chunking: boolean;
+  transcription_chunk_parallelism: number;
+  default_transcription_language: string;
+  whisper_runtime_options: WhisperRuntimeOptions;
+  llm_retry_options: LlmRetryOptions;
+  max_prompt_chars: number;
+  artifact_output_language: string;
+  auto_regenerate_artifacts: boolean;
+  /** Manual edits changing more than this percent of the words regenerate stale artifacts. */
+  auto_regenerate_threshold_percent: number;
+  max_ollama_jobs: number;
+  auto_normalize: boolean;
+  text_cleanup: TextCleanupOptions;
+  purge_grace_hours: number;
+  audio_safety_copies: number;
+  export_retention_count: number;
+  export_filename_template: string;
+  export_default_directory: string;
+  default_title_template: string;
+}
+
+/** Nested objects merge; `null` under `artifact_models` clears that override. */
+export type SettingsPatch = {
+  [K in keyof Settings]?: K extends "artifact_models"
+    ? Partial<Record<ArtifactType, string | null>>
+    : Settings[K] extends object
+      ? Partial<Settings[K]>
+      : Settings[K];
+};
+
+/** @deprecated Load `AppSettings`, the folder tree, and entry pages separately. */
+export interface BootstrapState extends AppSettings {
+  folders: Folder[];
+  entries: Entry[];
+  deprecated: true;
+}
+
+export interface FolderTreeNode extends Folder {
+  entry_count: number;
+}
+
+export interface EntryPage {
+  entries: Entry[];
+  total: number;
+  warnings: string[];
+}
+
+export interface WhisperRuntimeOptions {
+  use_gpu: boolean;
+  threads: number;
+  beam_size: number;
+}
+
+/** Whisper artifacts removed before a transcript revision is stored. */
+export interface TextCleanupOptions {
+  strip_noise_tags: boolean;
+  strip_timestamps: boolean;
+  collapse_blank_lines: boolean;
 }
 
 export interface EntryBundle {
+  is_locked: boolean;
+  metadata: Record<string, string>;
   transcript_revisions: TranscriptRevision[];
   artifact_revisions: ArtifactRevision[];
+  /** Artifact types whose latest revision is stale now; `is_stale` on older revisions is history. */
+  stale_artifact_types: string[];
+  /** Every recording session of the entry, oldest first. */
+  recording_sessions: RecordingSessionRecord[];
 }
 
 export interface RecordingSource {
   label: string;
   format: string;
   input: string;
+  /** Re-resolved to the device's current input right before recording starts. */
+  device_name?: string | null;
+  /** Volume correction in dB, between -30 and +30, applied before mixing. */
+  gain_db?: number | null;
+}
+
+export interface RecordingConfig {
+  recorder: "ffmpeg" | "screencapturekit";
+  sources: RecordingSource[];
+  auto_balance: boolean;
+  native_microphone: boolean;
+  appended: boolean;
+  sample_rate: number;
+  channels: number;
+  container: string;
+  /** Versions of the tools the session ran, e.g. `{ ffmpeg: "6.1.1" }`; absent on older sessions. */
+  tool_versions?: Record<string, string>;
+}
+
+export interface RecordingOutcome {
+  saved: boolean;
+  exit_code: number | null;
+  bytes: number;
+  duration_sec: number;
+  error: string | null;
+}
+
+export interface RecordingSessionRecord {
+  id: string;
+  entry_id: string;
+  started_at: string;
+  ended_at: string | null;
+  config: RecordingConfig;
+  /** Null while the session is still recording. */
+  outcome: RecordingOutcome | null;
+}
+
+export interface SourcePreset {
+  name: string;
+  sources: RecordingSource[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface RecordingSegment {
+  id: string;
+  entry_id: string;
+  path: string;
+  duration_sec: number;
+  recorded_at: string;
+  position: number;
 }
 
 export interface RecordingDevice {
@@ -90,4 +705,305 @@ export interface RecordingDevice {
 export interface RecordingMeter {
   bytes_written: number;
   level: number;
+  last_error: string | null;
+  /** Capture time so far, not counting pauses. */
+  elapsed_seconds: number;
+  /** Free disk space divided by the recent write rate; null until both are known. */
+  estimated_remaining_seconds: number | null;
+  /** Recent sample peak as linear amplitude, 1 being full scale. */
+  peak_level: number;
+  /** True while the input clipped within the last couple of seconds. */
+  clipping: boolean;
+  clipped_frames: number;
+  /** Also true when the session paused itself after the computer slept. */
+  paused: boolean;
+}
+
+/** A session that is recording right now, as `list_recording_sessions` reports it. */
+export interface ActiveRecording {
+  session_id: string;
+  entry_id: string;
+  /** File name of the segment being written. */
+  output_file: string;
+  paused: boolean;
+  elapsed_seconds: number;
+  bytes_written: number;
+  last_error: string | null;
+}
+
+/** Overall levels of a saved segment; the dB values are null for digital silence. */
+export interface RecordingLevels {
+  peak_db: number | null;
+  rms_db: number | null;
+  clipped_samples: number;
+  samples: number;
+}
+
+/** Payload of `recording://finalized`, sent once a stopped recording's segment is saved. */
+export interface RecordingFinalized {
+  session_id: string;
+  entry_id: string;
+  levels: RecordingLevels | null;
+  warning: string | null;
+}
+
+/** Payload of `recording://interrupted`, sent after the computer woke from sleep mid-recording. */
+export interface RecordingInterrupted {
+  session_id: string;
+  entry_id: string;
+  slept_seconds: number;
+  /** The session was paused so the user can choose to resume; false if pausing failed. */
+  paused: boolean;
+  /** The recorder died during sleep; stopping still saves what was captured before. */
+  recorder_exited: boolean;
+}
+
+export type ScheduledRecordingStatus = "pending" | "starting" | "started" | "failed" | "cancelled" | "expired";
+
+export interface ScheduledRecording {
+  id: string;
+  entry_id: string;
+  sources: RecordingSource[];
+  auto_balance: boolean;
+  /** RFC 3339, in UTC. */
+  start_at: string;
+  status: ScheduledRecordingStatus;
+  session_id: string | null;
+  error: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+/** Payload of `recording://scheduled_started`. */
+export interface ScheduledRecordingStarted {
+  schedule_id: string;
+  entry_id: string;
+  session_id: string;
+}
+
+/** Payload of `recording://scheduled_failed`; `expired` means the start time had long passed. */
+export interface ScheduledRecordingFailed {
+  schedule_id: string;
+  entry_id: string;
+  error: string;
+  expired: boolean;
+}
+
+/** Payload of `recording://source_lost`; `devices://changed` carries `RecordingDevice[]`. */
+export interface RecordingSourceLost {
+  session_id: string;
+  device_name: string;
+}
+
+/** Returned by the playback commands and emitted as `playback://position` about four times a second. */
+export interface PlaybackStatus {
+  entry_id: string;
+  position_ms: number;
+  duration_ms: number;
+  is_playing: boolean;
+}
+
+export interface LlmRetryOptions {
+  max_retries: number;
+  initial_backoff_ms: number;
+  deadline_secs: number;
+}
+
+export interface ArtifactRetrying {
+  entry_id: string;
+  artifact_type: ArtifactType;
+  attempt: number;
+  max_retries: number;
+  delay_ms: number;
+  reason: string;
+}
+
+export interface ArtifactGenerated {
+  entry_id: string;
+  artifact_type: ArtifactType;
+  version: number;
+  truncated_chars: number | null;
+  warning: string | null;
+}
+
+export interface ModelNotFoundError {
+  code: "MODEL_NOT_FOUND";
+  message: string;
+  model: string;
+}
+
+export interface TranscriptionProgress {
+  entry_id: string;
+  completed_chunks: number;
+  total_chunks: number;
+}
+
+export interface TranscriptionCompleted {
+  entry_id: string;
+  version: number;
+  language: string;
+  warnings: string[];
+}
+
+export interface Diagnostics {
+  ffmpeg_available: boolean;
+  whisper_cli_available: boolean;
+  whisper_available: boolean;
+  tool_versions: Record<string, string>;
+  ollama_reachable: boolean;
+  whisper_model: string;
+  whisper_engine: string;
+  whisper_runtime_options: WhisperRuntimeOptions;
+  whisper_flags: string[];
+  model_info: ModelInfo[];
+  failed_recordings: RecordingSessionRecord[];
+}
+
+export interface ModelInfo {
+  model: string;
+  installed: boolean;
+  parameter_size: string | null;
+  context_length: number | null;
+  capabilities: string[];
+  checked_at: number;
+}
+
+export type ClipboardFormat = "markdown" | "plain";
+
+export type JobKind = "whisper" | "ollama" | "embedding" | "export";
+
+export type JobBlocked = "paused" | "recording" | "busy";
+
+export interface JobKindState {
+  kind: JobKind;
+  limit: number;
+  running: number;
+  waiting: number;
+  blocked: JobBlocked | null;
+}
+
+/** What a job command resolves to; `queued` work reports through `onDeferredJobFinished` once it has run. */
+export type JobOutcome<T> = { status: "done"; result: T } | { status: "queued" };
+
+export interface DeferredJobFinished {
+  entry_id: string;
+  kind: JobKind;
+  label: string;
+  error: string | null;
+}
+
+export interface JobSchedulerState {
+  paused: boolean;
+  recording: boolean;
+  kinds: JobKindState[];
+}
+
+export type RedactionCategory = "email" | "phone" | "number" | "name";
+
+export interface RedactionMatch {
+  category: RedactionCategory;
+  text: string;
+  start: number;
+  end: number;
+}
+
+export interface BusyError {
+  code: "BUSY";
+  message: string;
+  entry_id: string;
+  operation: EntryOperation;
+}
+
+export interface ConflictError<T> {
+  code: "CONFLICT";
+  message: string;
+  latest: T | null;
+}
+
+export interface DataDirMigrationProgress {
+  copied_files: number;
+  total_files: number;
+  copied_bytes: number;
+  total_bytes: number;
+}
+
+export interface DuplicateError {
+  code: "DUPLICATE";
+  message: string;
+  existing_entry_id: string;
+}
+
+export interface EntryLockedError {
+  code: "ENTRY_LOCKED";
+  message: string;
+  entry_id: string;
+}
+
+export type EntryOperation =
+  | "recording"
+  | "transcription"
+  | "transcript_cleanup"
+  | "redaction"
+  | "artifact_generation"
+  | "transcript_edit"
+  | "artifact_edit"
+  | "call_dynamics"
+  | "chapters"
+  | "split"
+  | "merge"
+  | "prompt_test"
+  | "rebuild_recording"
+  | "trim"
+  | "normalize";
+
+export interface TranscriptLocation {
+  segment_index: number;
+  at_ms: number;
+}
+
+export interface TimeLocation {
+  segment_index: number;
+  start_ms: number;
+  end_ms: number;
+  /** Character (not UTF-16) range of the line's spoken text in the latest transcript. */
+  char_start: number;
+  char_end: number;
+}
+
+/** Returned by the locate commands when the latest transcript carries no timestamps. */
+export interface NoTimingError {
+  code: "NO_TIMING";
+  message: string;
+}
+
+export interface LockedError {
+  code: "LOCKED";
+  message: string;
+}
+
+/** Returned when a folder, entry, or preset name is rejected; `problems` lists each rule it broke. */
+export interface ValidationError {
+  code: "VALIDATION";
+  message: string;
+  field: string;
+  problems: string[];
+}
+
+/** What startup did about an app.db it found damaged; the damaged file is kept at `quarantined_path`. */
+export interface DatabaseRecovery {
+  quarantined_path: string;
+  damage: string;
+  /** Rows copied out of the damaged file, by table. */
+  salvaged_rows: Record<string, number>;
+  /** Tables that could be read only in part or not at all. */
+  damaged_tables: string[];
+  recovered_at: string;
+}
+
+export interface OnboardingStatus {
+  /** app.db was damaged and has been rebuilt from what could still be read. */
+  database_recovered: boolean;
+  /** app.db was damaged and nothing could be read, so the library starts empty. */
+  database_reset: boolean;
+  database_recovery: DatabaseRecovery | null;
 }